@@ -0,0 +1,91 @@
+use crate::XCEvent;
+use async_stream::stream;
+use process_stream::{Stream, StreamExt};
+use std::pin::Pin;
+use tokio::sync::broadcast::{self, error::RecvError};
+use tokio::task::JoinHandle;
+
+/// Fan out a single event stream, e.g. from [`crate::XCLogger::spawn_and_stream_events`], to
+/// several independent subscribers.
+///
+/// Events are buffered up to the channel capacity. Subscribers falling further behind skip the
+/// oldest events with a warning instead of stalling the build.
+pub struct XCBroadcast<T = XCEvent> {
+    sender: broadcast::Sender<T>,
+}
+
+impl<T: Clone + Send + 'static> XCBroadcast<T> {
+    /// Create broadcast buffering up to `capacity` events per subscriber
+    pub fn new(capacity: usize) -> Self {
+        let (sender, _) = broadcast::channel(capacity.max(1));
+        Self { sender }
+    }
+
+    /// Subscribe to events sent after this call. The stream ends once the source stream ends.
+    pub fn subscribe(&self) -> Pin<Box<dyn Stream<Item = T> + Send>> {
+        let mut receiver = self.sender.subscribe();
+        stream! {
+            loop {
+                match receiver.recv().await {
+                    Ok(event) => yield event,
+                    Err(RecvError::Lagged(skipped)) => {
+                        tracing::warn!("Subscriber lagged behind, skipped {skipped} events");
+                    }
+                    Err(RecvError::Closed) => break,
+                }
+            }
+        }
+        .boxed()
+    }
+
+    /// Get number of current subscribers
+    pub fn subscribers(&self) -> usize {
+        self.sender.receiver_count()
+    }
+
+    /// Drive given stream to its end in a background task, sending every event to subscribers.
+    ///
+    /// The stream is consumed even when nobody is subscribed.
+    pub fn spawn<S>(self, mut stream: S) -> JoinHandle<()>
+    where
+        S: Stream<Item = T> + Send + Unpin + 'static,
+    {
+        tokio::spawn(async move {
+            while let Some(event) = stream.next().await {
+                let _ = self.sender.send(event);
+            }
+        })
+    }
+}
+
+#[tokio::test]
+#[tracing_test::traced_test]
+async fn test_broadcast() {
+    use crate::XCLogger;
+
+    let mut logger = XCLogger::from_reader(
+        &b"CompileSwift normal arm64 /path/to/App.swift (in target 'App' from project 'App')
+/path/to/App.swift:1:1: warning: unused variable 'foo'
+** BUILD SUCCEEDED **
+"[..],
+    );
+    let broadcast = XCBroadcast::new(16);
+    let editor = broadcast.subscribe();
+    let annotator = broadcast.subscribe();
+    let lagging = XCBroadcast::new(1);
+    let mut slow = lagging.subscribe();
+
+    assert_eq!(broadcast.subscribers(), 2);
+    let events = logger.spawn_and_stream_events().unwrap();
+    broadcast.spawn(events).await.unwrap();
+    lagging.spawn(tokio_stream::iter(0..3)).await.unwrap();
+
+    let editor = editor.collect::<Vec<_>>().await;
+    let annotator = annotator.collect::<Vec<_>>().await;
+
+    assert!(editor.last().unwrap().is_finished());
+    assert_eq!(editor.len(), annotator.len());
+    assert_eq!(slow.next().await, Some(2));
+    assert_eq!(slow.next().await, None);
+    assert!(logs_contain("skipped 2 events"));
+}
@@ -0,0 +1,339 @@
+use anyhow::{bail, Result};
+use std::path::{Path, PathBuf};
+
+/// xcodebuild action
+#[derive(Debug, Clone, Copy, PartialEq, Eq, derive_is_enum_variant::is_enum_variant)]
+pub enum XCBuildAction {
+    /// `build`
+    Build,
+    /// `build-for-testing`
+    BuildForTesting,
+    /// `test`
+    Test,
+    /// `test-without-building`
+    TestWithoutBuilding,
+    /// `analyze`
+    Analyze,
+    /// `archive`
+    Archive,
+    /// `clean`
+    Clean,
+}
+
+impl XCBuildAction {
+    /// Action name as passed to xcodebuild
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Build => "build",
+            Self::BuildForTesting => "build-for-testing",
+            Self::Test => "test",
+            Self::TestWithoutBuilding => "test-without-building",
+            Self::Analyze => "analyze",
+            Self::Archive => "archive",
+            Self::Clean => "clean",
+        }
+    }
+
+    /// Whether the action runs tests
+    pub fn runs_tests(&self) -> bool {
+        matches!(self, Self::Test | Self::TestWithoutBuilding)
+    }
+
+    /// Whether xcodebuild requires a scheme for the action
+    pub fn requires_scheme(&self) -> bool {
+        matches!(
+            self,
+            Self::BuildForTesting | Self::Test | Self::TestWithoutBuilding | Self::Archive
+        )
+    }
+}
+
+/// Typed xcodebuild invocation, producing arguments for [`crate::XCLogger`] and
+/// [`crate::XCBuildSettings`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct XCBuildCommand {
+    actions: Vec<XCBuildAction>,
+    project: Option<PathBuf>,
+    workspace: Option<PathBuf>,
+    scheme: Option<String>,
+    target: Option<String>,
+    configuration: Option<String>,
+    destinations: Vec<String>,
+    sdk: Option<String>,
+    only_testing: Vec<String>,
+    skip_testing: Vec<String>,
+    result_bundle_path: Option<PathBuf>,
+    settings: Vec<(String, String)>,
+}
+
+impl XCBuildCommand {
+    /// Create command running given actions in order
+    pub fn new<I: IntoIterator<Item = XCBuildAction>>(actions: I) -> Self {
+        Self {
+            actions: actions.into_iter().collect(),
+            ..Default::default()
+        }
+    }
+
+    /// Set `-project`
+    pub fn project<P: AsRef<Path>>(mut self, path: P) -> Self {
+        self.project = Some(path.as_ref().to_path_buf());
+        self
+    }
+
+    /// Set `-workspace`
+    pub fn workspace<P: AsRef<Path>>(mut self, path: P) -> Self {
+        self.workspace = Some(path.as_ref().to_path_buf());
+        self
+    }
+
+    /// Set `-scheme`
+    pub fn scheme<S: Into<String>>(mut self, scheme: S) -> Self {
+        self.scheme = Some(scheme.into());
+        self
+    }
+
+    /// Set `-target`
+    pub fn target<S: Into<String>>(mut self, target: S) -> Self {
+        self.target = Some(target.into());
+        self
+    }
+
+    /// Set `-configuration`, e.g. `Debug`
+    pub fn configuration<S: Into<String>>(mut self, configuration: S) -> Self {
+        self.configuration = Some(configuration.into());
+        self
+    }
+
+    /// Add `-destination` specifier, e.g. [`crate::XCDestination`] or
+    /// `platform=iOS Simulator,name=iPhone 14`
+    pub fn destination<S: Into<String>>(mut self, specifier: S) -> Self {
+        self.destinations.push(specifier.into());
+        self
+    }
+
+    /// Set `-sdk`, e.g. `iphonesimulator`
+    pub fn sdk<S: Into<String>>(mut self, sdk: S) -> Self {
+        self.sdk = Some(sdk.into());
+        self
+    }
+
+    /// Add `-only-testing` identifier, e.g. `AppTests/FooTests/testBar`
+    pub fn only_testing<S: Into<String>>(mut self, identifier: S) -> Self {
+        self.only_testing.push(identifier.into());
+        self
+    }
+
+    /// Add `-skip-testing` identifier, e.g. `AppUITests`
+    pub fn skip_testing<S: Into<String>>(mut self, identifier: S) -> Self {
+        self.skip_testing.push(identifier.into());
+        self
+    }
+
+    /// Set `-resultBundlePath`
+    pub fn result_bundle_path<P: AsRef<Path>>(mut self, path: P) -> Self {
+        self.result_bundle_path = Some(path.as_ref().to_path_buf());
+        self
+    }
+
+    /// Add build setting override, e.g. `SYMROOT=/path/to/build`
+    pub fn setting<K: Into<String>, V: Into<String>>(mut self, key: K, value: V) -> Self {
+        self.settings.push((key.into(), value.into()));
+        self
+    }
+
+    /// Create command running only given tests with `test-without-building`, writing the
+    /// result bundle, if any, next to the original one.
+    pub(crate) fn retry<I: IntoIterator<Item = String>>(&self, attempt: usize, tests: I) -> Self {
+        let result_bundle_path = self.result_bundle_path.as_ref().map(|path| {
+            let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+            path.with_file_name(format!("{stem}-retry-{attempt}.xcresult"))
+        });
+
+        Self {
+            actions: vec![XCBuildAction::TestWithoutBuilding],
+            only_testing: tests.into_iter().collect(),
+            skip_testing: vec![],
+            result_bundle_path,
+            ..self.clone()
+        }
+    }
+
+    /// Check for conflicting options
+    pub fn validate(&self) -> Result<()> {
+        if self.project.is_some() && self.workspace.is_some() {
+            bail!("Only one of -project or -workspace can be specified");
+        }
+
+        if self.scheme.is_some() && self.target.is_some() {
+            bail!("Only one of -scheme or -target can be specified");
+        }
+
+        if self.scheme.is_none() {
+            if self.workspace.is_some() {
+                bail!("-workspace requires -scheme");
+            }
+            if let Some(action) = self.actions.iter().find(|a| a.requires_scheme()) {
+                bail!("{} requires -scheme", action.as_str());
+            }
+        }
+
+        let runs_tests = self.actions.iter().any(XCBuildAction::runs_tests);
+        if !runs_tests && !(self.only_testing.is_empty() && self.skip_testing.is_empty()) {
+            bail!("-only-testing and -skip-testing require test or test-without-building");
+        }
+
+        if let Some(identifier) = self
+            .only_testing
+            .iter()
+            .find(|i| self.skip_testing.contains(i))
+        {
+            bail!("{identifier} is passed to both -only-testing and -skip-testing");
+        }
+
+        if let Some((key, _)) = self
+            .settings
+            .iter()
+            .find(|(k, _)| k.is_empty() || k.contains(|c: char| c == '=' || c.is_whitespace()))
+        {
+            bail!("Invalid build setting name {key:?}");
+        }
+
+        Ok(())
+    }
+
+    /// Arguments for [`crate::XCLogger::new`]
+    pub fn args(&self) -> Result<Vec<String>> {
+        self.validate()?;
+
+        let mut args = self
+            .actions
+            .iter()
+            .map(|a| a.as_str().to_string())
+            .collect::<Vec<_>>();
+        args.extend(self.common_args());
+
+        for identifier in self.only_testing.iter() {
+            args.push(format!("-only-testing:{identifier}"));
+        }
+        for identifier in self.skip_testing.iter() {
+            args.push(format!("-skip-testing:{identifier}"));
+        }
+        if let Some(ref path) = self.result_bundle_path {
+            args.extend(["-resultBundlePath".into(), path.display().to_string()]);
+        }
+        args.extend(self.settings.iter().map(|(k, v)| format!("{k}={v}")));
+
+        Ok(args)
+    }
+
+    /// Arguments for [`crate::XCBuildSettings::new`], leaving out actions and testing options
+    pub fn build_settings_args(&self) -> Result<Vec<String>> {
+        self.validate()?;
+
+        let mut args = self.common_args();
+        args.extend(self.settings.iter().map(|(k, v)| format!("{k}={v}")));
+        Ok(args)
+    }
+
+    fn common_args(&self) -> Vec<String> {
+        let mut args = vec![];
+        let paths = [("-project", &self.project), ("-workspace", &self.workspace)];
+        for (flag, path) in paths {
+            if let Some(path) = path {
+                args.extend([flag.into(), path.display().to_string()]);
+            }
+        }
+
+        let options = [
+            ("-scheme", &self.scheme),
+            ("-target", &self.target),
+            ("-configuration", &self.configuration),
+            ("-sdk", &self.sdk),
+        ];
+        for (flag, value) in options {
+            if let Some(value) = value {
+                args.extend([flag.into(), value.clone()]);
+            }
+        }
+
+        for destination in self.destinations.iter() {
+            args.extend(["-destination".into(), destination.clone()]);
+        }
+        args
+    }
+}
+
+#[test]
+fn test_build_command_args() {
+    let command = XCBuildCommand::new([XCBuildAction::Clean, XCBuildAction::Test])
+        .workspace("App.xcworkspace")
+        .scheme("App")
+        .configuration("Debug")
+        .destination("platform=iOS Simulator,name=iPhone 14")
+        .only_testing("AppTests/FooTests")
+        .skip_testing("AppTests/FooTests/testSlow")
+        .result_bundle_path("/tmp/App.xcresult")
+        .setting("SYMROOT", "/tmp/build");
+
+    assert_eq!(
+        command.args().unwrap(),
+        vec![
+            "clean",
+            "test",
+            "-workspace",
+            "App.xcworkspace",
+            "-scheme",
+            "App",
+            "-configuration",
+            "Debug",
+            "-destination",
+            "platform=iOS Simulator,name=iPhone 14",
+            "-only-testing:AppTests/FooTests",
+            "-skip-testing:AppTests/FooTests/testSlow",
+            "-resultBundlePath",
+            "/tmp/App.xcresult",
+            "SYMROOT=/tmp/build"
+        ]
+    );
+    assert_eq!(
+        command.build_settings_args().unwrap(),
+        vec![
+            "-workspace",
+            "App.xcworkspace",
+            "-scheme",
+            "App",
+            "-configuration",
+            "Debug",
+            "-destination",
+            "platform=iOS Simulator,name=iPhone 14",
+            "SYMROOT=/tmp/build"
+        ]
+    );
+}
+
+#[test]
+fn test_build_command_conflicts() {
+    use XCBuildAction::*;
+
+    let conflicts = [
+        XCBuildCommand::new([Build])
+            .project("App.xcodeproj")
+            .workspace("App.xcworkspace"),
+        XCBuildCommand::new([Build]).scheme("App").target("App"),
+        XCBuildCommand::new([Build]).workspace("App.xcworkspace"),
+        XCBuildCommand::new([Archive]).target("App"),
+        XCBuildCommand::new([Build])
+            .scheme("App")
+            .only_testing("AppTests"),
+        XCBuildCommand::new([Test])
+            .scheme("App")
+            .only_testing("AppTests")
+            .skip_testing("AppTests"),
+        XCBuildCommand::new([Build]).setting("SYMROOT =", "/tmp"),
+    ];
+
+    for command in conflicts {
+        assert!(command.args().is_err(), "{command:?}");
+    }
+}
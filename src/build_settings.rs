@@ -5,6 +5,7 @@ use std::{
     process::Stdio,
 };
 
+use crate::XCRunner;
 use anyhow::Result;
 use tap::Pipe;
 
@@ -191,9 +192,22 @@ impl XCBuildSettings {
         I: IntoIterator<Item = S> + Send,
         S: AsRef<std::ffi::OsStr> + Send,
     {
-        let mut process = Process::new("/usr/bin/xcodebuild");
+        Self::new_with_runner(&XCRunner::default(), root, args).await
+    }
 
-        process.current_dir(root);
+    /// Generate Build Settings from given root and build arguments using xcodebuild configured by
+    /// [`XCRunner`]
+    pub async fn new_with_runner<P, I, S>(
+        runner: &XCRunner,
+        root: P,
+        args: I,
+    ) -> Result<XCBuildSettings>
+    where
+        P: AsRef<Path> + Send,
+        I: IntoIterator<Item = S> + Send,
+        S: AsRef<std::ffi::OsStr> + Send,
+    {
+        let mut process = Process::from(tokio::process::Command::from(runner.command(root)));
 
         process.args(args);
         process.arg("-showBuildSettings");
@@ -222,9 +236,22 @@ impl XCBuildSettings {
         I: IntoIterator<Item = S> + Send,
         S: AsRef<std::ffi::OsStr> + Send,
     {
-        let mut process = std::process::Command::new("/usr/bin/xcodebuild");
+        Self::new_sync_with_runner(&XCRunner::default(), root, args)
+    }
 
-        process.current_dir(root);
+    /// Generate Build Settings from given root and build arguments using xcodebuild configured by
+    /// [`XCRunner`]
+    pub fn new_sync_with_runner<P, I, S>(
+        runner: &XCRunner,
+        root: P,
+        args: I,
+    ) -> Result<XCBuildSettings>
+    where
+        P: AsRef<Path> + Send,
+        I: IntoIterator<Item = S> + Send,
+        S: AsRef<std::ffi::OsStr> + Send,
+    {
+        let mut process = runner.command(root);
 
         process.args(args);
         process.arg("-showBuildSettings");
@@ -0,0 +1,52 @@
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tokio::sync::Notify;
+
+/// Reason a build was stopped before xcodebuild exited on its own
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    derive_is_enum_variant::is_enum_variant,
+)]
+pub enum XCCancelReason {
+    /// Cancelled through [`XCCancelHandle::cancel`]
+    Cancelled,
+    /// Build exceeded the overall timeout
+    Timeout,
+    /// Build printed no output for longer than the idle timeout
+    Idle,
+    /// Killed right away through the `process_stream` aborter, without SIGINT
+    Aborted,
+}
+
+impl std::fmt::Display for XCCancelReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Cancelled => write!(f, "Cancelled"),
+            Self::Timeout => write!(f, "Timed out"),
+            Self::Idle => write!(f, "No output"),
+            Self::Aborted => write!(f, "Aborted"),
+        }
+    }
+}
+
+/// Handle to gracefully cancel a running build: xcodebuild gets SIGINT first, so it can write
+/// its result bundle, and SIGKILL once the grace period ends.
+#[derive(Debug, Clone, Default)]
+pub struct XCCancelHandle(Arc<Notify>);
+
+impl XCCancelHandle {
+    /// Cancel the build. Cancelling before the build is spawned cancels it right away.
+    pub fn cancel(&self) {
+        self.0.notify_one()
+    }
+
+    pub(crate) async fn cancelled(&self) {
+        self.0.notified().await
+    }
+}
@@ -51,11 +51,8 @@ impl XCCompileCommand {
         // If clang and no file captured then return none
         // TODO: make sure this is always the case!
         if is_clang {
-            if let Some(file) = cap!(r"-c\s(.*)-o", args).map(|(_, c)| c.trim()) {
-                command.file = Some(file.into());
-            } else {
-                return None;
-            }
+            let file = cap!(r"-c\s(.*)-o", args).map(|(_, c)| c.trim())?;
+            command.file = Some(file.into());
         } else {
             // Module name is required for swiftc, return early?
             command.name = cap!(r"-module-name\s(\w+)\s", args).map(|(_, c)| c.into());
@@ -77,13 +74,13 @@ impl XCCompileCommand {
     }
 
     /// Generate a map of filespaths in workspaces and their compilation flags
-    pub fn compile_flags<'a>(&'a self) -> Result<HashMap<PathBuf, XCCompileArgs>> {
+    pub fn compile_flags(&self) -> Result<HashMap<PathBuf, XCCompileArgs>> {
         let mut info = HashMap::default();
         let flags = XCCompileArgs::try_from_str(&self.command)?;
 
         // Swift File Lists
         self.file_lists.iter().for_each(|path| {
-            match util::get_files_list(&path) {
+            match util::get_files_list(path) {
                 Ok(file_list) => {
                     file_list.into_iter().for_each(|file_path: PathBuf| {
                         info.insert(file_path, flags.clone());
@@ -94,11 +91,11 @@ impl XCCompileCommand {
         });
 
         // Swift Module Files
-        self.files.as_ref().map(|files| {
+        if let Some(files) = self.files.as_ref() {
             files.iter().for_each(|file| {
                 info.insert(file.clone(), flags.clone());
             })
-        });
+        }
 
         // Single File Command
         self.file
@@ -61,7 +61,10 @@ impl XCCompileArgs {
                     .as_ref()
                     .to_str()
                     .ok_or_else(|| anyhow::anyhow!("Unable to convert filepath to path"))?
-                    .pipe(|f| vec![f.into(), "-sdk".into(), util::SDKPATH.into()])
+                    .pipe(|f| {
+                        let sdk = crate::XCSdk::macosx_path();
+                        vec![f.into(), "-sdk".into(), sdk.to_string_lossy().into()]
+                    })
                     .pipe(Self)
                     .pipe(Ok);
             }
@@ -13,7 +13,7 @@ fn get_compile_commands(content: &str) -> XCCompilationDatabase {
 
 async fn get_compile_commands_from_local_case_d() -> XCCompilationDatabase {
     let root = "/Users/tami5/repos/swift/yabaimaster";
-    let mut logger = XCLogger::new(root, &[
+    let mut logger = XCLogger::new(root, [
         "clean",
         "build",
         "-configuration",
@@ -3,8 +3,6 @@ use anyhow::Result;
 use std::path::{Path, PathBuf};
 use tap::Pipe;
 
-pub const SDKPATH: &str = "/Applications/Xcode.app/Contents/Developer/Platforms/MacOSX.platform/Developer/SDKs/MacOSX.sdk/";
-
 /// Get all files in SwiftFileList file.
 pub(crate) fn get_files_list<T, P>(file_lists: P) -> Result<Vec<T>>
 where
@@ -24,7 +22,7 @@ pub(crate) fn find_header_dirs(root: &Path) -> Result<(Vec<String>, Vec<String>)
     wax::walk("**/*.h", root)?
         .flatten()
         .enumerate()
-        .map(|(i, entry)| {
+        .filter_map(|(i, entry)| {
             entry
                 .path()
                 .ancestors()
@@ -41,7 +39,6 @@ pub(crate) fn find_header_dirs(root: &Path) -> Result<(Vec<String>, Vec<String>)
                     }
                 })
         })
-        .flatten()
         .unzip()
         .pipe(|(dirs, frameworks): (Vec<_>, Vec<_>)| {
             let dirs = dirs.into_iter().flatten().collect();
@@ -53,13 +50,12 @@ pub(crate) fn find_header_dirs(root: &Path) -> Result<(Vec<String>, Vec<String>)
 pub fn find_swift_files(project_root: &Path) -> Result<Vec<String>> {
     wax::walk("**/*.swift", project_root)?
         .enumerate()
-        .map(|(i, entry)| {
+        .filter_map(|(i, entry)| {
             entry.ok()?.path().to_str()?.to_string().pipe(|path| {
                 tracing::trace!("{i}: {path}");
                 Some(path)
             })
         })
-        .flatten()
         .collect::<Vec<_>>()
         .pipe(Ok)
 }
@@ -165,7 +161,7 @@ pub(crate) fn generate_compile_args_from_root(
             flags_collect.append(additional_flags)
         }
     }
-    return flags_collect.pipe(XCCompileArgs).pipe(Result::Ok);
+    flags_collect.pipe(XCCompileArgs).pipe(Result::Ok)
 }
 
 /// Get Additional flags from an optional flags_path.
@@ -0,0 +1,226 @@
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// xcodebuild destination, formatted into `-destination` specifier and parsed from
+/// `-showdestinations` listings
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct XCDestination {
+    /// Platform, e.g. `iOS Simulator`
+    pub platform: Option<String>,
+    /// Architecture, e.g. `arm64`
+    pub arch: Option<String>,
+    /// Platform variant, e.g. `Mac Catalyst`
+    pub variant: Option<String>,
+    /// Device identifier
+    pub id: Option<String>,
+    /// OS version, e.g. `17.2` or `latest`
+    pub os: Option<String>,
+    /// Device name, e.g. `iPhone 15`
+    pub name: Option<String>,
+    /// Reason the destination is ineligible, if any
+    pub error: Option<String>,
+}
+
+impl XCDestination {
+    /// Create destination for given platform
+    pub fn new<S: Into<String>>(platform: S) -> Self {
+        Self {
+            platform: Some(platform.into()),
+            ..Default::default()
+        }
+    }
+
+    /// Set device identifier
+    pub fn id<S: Into<String>>(mut self, id: S) -> Self {
+        self.id = Some(id.into());
+        self
+    }
+
+    /// Set device name
+    pub fn name<S: Into<String>>(mut self, name: S) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Set OS version
+    pub fn os<S: Into<String>>(mut self, os: S) -> Self {
+        self.os = Some(os.into());
+        self
+    }
+
+    /// Set architecture
+    pub fn arch<S: Into<String>>(mut self, arch: S) -> Self {
+        self.arch = Some(arch.into());
+        self
+    }
+
+    /// Set platform variant
+    pub fn variant<S: Into<String>>(mut self, variant: S) -> Self {
+        self.variant = Some(variant.into());
+        self
+    }
+
+    /// Parse listing line, e.g. `{ platform:iOS Simulator, id:5C1E3B0A, OS:17.2, name:iPhone 15 }`
+    pub fn from_listing_line(line: &str) -> Option<Self> {
+        let line = line.trim().strip_prefix('{')?.strip_suffix('}')?.trim();
+        let mut pairs: Vec<(&str, String)> = vec![];
+
+        // Values such as `error:` may contain ", ", so only segments starting with a key are
+        // treated as new pairs.
+        for segment in line.split(", ") {
+            match segment.split_once(':') {
+                Some((key, value)) if key.chars().all(|c| c.is_ascii_alphabetic()) => {
+                    pairs.push((key, value.to_string()))
+                }
+                _ => match pairs.last_mut() {
+                    Some((_, value)) => {
+                        value.push_str(", ");
+                        value.push_str(segment);
+                    }
+                    None => return None,
+                },
+            }
+        }
+
+        let mut destination = Self::default();
+        for (key, value) in pairs {
+            destination.set(key, value);
+        }
+        Some(destination)
+    }
+
+    /// Parse destinations listed under `Available destinations for the "X" scheme:`, e.g. in
+    /// `-showdestinations` output or destination mismatch errors
+    pub fn available(content: &str) -> Vec<Self> {
+        Self::section(content, "Available destinations")
+    }
+
+    /// Parse destinations listed under `Ineligible destinations for the "X" scheme:`
+    pub fn ineligible(content: &str) -> Vec<Self> {
+        Self::section(content, "Ineligible destinations")
+    }
+
+    fn section(content: &str, header: &str) -> Vec<Self> {
+        content
+            .lines()
+            .map(str::trim)
+            .skip_while(|l| !l.starts_with(header))
+            .skip(1)
+            .take_while(|l| l.starts_with('{'))
+            .filter_map(Self::from_listing_line)
+            .collect()
+    }
+
+    fn set(&mut self, key: &str, value: String) -> bool {
+        let field = match key.to_lowercase().as_str() {
+            "platform" => &mut self.platform,
+            "arch" => &mut self.arch,
+            "variant" => &mut self.variant,
+            "id" => &mut self.id,
+            "os" => &mut self.os,
+            "name" => &mut self.name,
+            "error" => &mut self.error,
+            _ => return false,
+        };
+        *field = Some(value);
+        true
+    }
+}
+
+impl Display for XCDestination {
+    /// Format as `-destination` specifier, e.g. `platform=iOS Simulator,OS=17.2,name=iPhone 15`
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let pairs = [
+            ("platform", &self.platform),
+            ("arch", &self.arch),
+            ("variant", &self.variant),
+            ("id", &self.id),
+            ("OS", &self.os),
+            ("name", &self.name),
+        ];
+        let specifier = pairs
+            .into_iter()
+            .filter_map(|(key, value)| Some(format!("{key}={}", value.as_ref()?)))
+            .collect::<Vec<_>>()
+            .join(",");
+        write!(f, "{specifier}")
+    }
+}
+
+impl FromStr for XCDestination {
+    type Err = anyhow::Error;
+
+    /// Parse `-destination` specifier, e.g. `platform=iOS Simulator,name=iPhone 15`
+    fn from_str(specifier: &str) -> Result<Self> {
+        let mut destination = Self::default();
+        for pair in specifier.split(',').filter(|p| !p.trim().is_empty()) {
+            let (key, value) = match pair.split_once('=') {
+                Some(pair) => pair,
+                None => bail!("Invalid destination specifier pair {pair:?}"),
+            };
+            if key.trim().eq_ignore_ascii_case("error")
+                || !destination.set(key.trim(), value.trim().to_string())
+            {
+                bail!("Unknown destination specifier key {key:?}");
+            }
+        }
+        if destination == Self::default() {
+            bail!("Empty destination specifier");
+        }
+        Ok(destination)
+    }
+}
+
+impl From<XCDestination> for String {
+    fn from(destination: XCDestination) -> Self {
+        destination.to_string()
+    }
+}
+
+#[test]
+fn test_destination_specifier() {
+    let destination = XCDestination::new("iOS Simulator")
+        .os("17.2")
+        .name("iPhone 15");
+    let specifier = "platform=iOS Simulator,OS=17.2,name=iPhone 15";
+
+    assert_eq!(destination.to_string(), specifier);
+    assert_eq!(specifier.parse::<XCDestination>().unwrap(), destination);
+    assert!("platform=iOS,model=iPhone"
+        .parse::<XCDestination>()
+        .is_err());
+    assert!("".parse::<XCDestination>().is_err());
+}
+
+#[test]
+fn test_destination_listing() {
+    let content = r#"
+	Available destinations for the "App" scheme:
+		{ platform:macOS, arch:arm64, variant:Mac Catalyst, id:00006000-001A, name:My Mac }
+		{ platform:iOS Simulator, id:5C1E3B0A, OS:17.2, name:iPhone 15 }
+
+	Ineligible destinations for the "App" scheme:
+		{ platform:iOS, id:dvtdevice-DVTiPhonePlaceholder-iphoneos:placeholder, name:Any iOS Device, error:iOS 17.2 is not installed. To use with Xcode, first download and install the platform }
+"#;
+    let available = XCDestination::available(content);
+    let ineligible = XCDestination::ineligible(content);
+
+    assert_eq!(available.len(), 2);
+    assert_eq!(available[0].variant.as_deref(), Some("Mac Catalyst"));
+    assert_eq!(available[0].arch.as_deref(), Some("arm64"));
+    assert_eq!(available[1].os.as_deref(), Some("17.2"));
+    assert_eq!(available[1].id.as_deref(), Some("5C1E3B0A"));
+    assert_eq!(ineligible.len(), 1);
+    assert_eq!(
+        ineligible[0].id.as_deref(),
+        Some("dvtdevice-DVTiPhonePlaceholder-iphoneos:placeholder")
+    );
+    assert_eq!(
+        ineligible[0].error.as_deref(),
+        Some(
+            "iOS 17.2 is not installed. To use with Xcode, first download and install the platform"
+        )
+    );
+}
@@ -0,0 +1,313 @@
+use crate::{XCCancelHandle, XCEvent, XCLogger};
+use anyhow::{Context, Result};
+use async_stream::stream;
+use parity_tokio_ipc::Endpoint;
+use process_stream::{Stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader, Lines};
+use tokio::io::{ReadHalf, WriteHalf};
+use tokio::sync::broadcast::{self, error::RecvError};
+use tokio::task::JoinHandle;
+
+/// Number of events buffered for each connected client
+const CLIENT_CAPACITY: usize = 1024;
+
+/// Number of most recent events replayed to clients joining late
+const HISTORY_CAPACITY: usize = 10_000;
+
+/// Control message sent by [`XCIpcClient`] to [`XCIpcServer`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum XCIpcRequest {
+    /// Gracefully cancel the build, see [`XCCancelHandle`]
+    Cancel,
+}
+
+/// Events published so far, and the channel publishing the next ones until the build ends
+struct XCIpcHistory {
+    lines: VecDeque<String>,
+    sender: Option<broadcast::Sender<String>>,
+}
+
+/// Stop accepting clients, close connected ones and remove the socket once dropped, even if the
+/// build stream is dropped before it ended.
+struct XCIpcGuard {
+    accept: JoinHandle<()>,
+    history: Arc<Mutex<XCIpcHistory>>,
+    path: PathBuf,
+}
+
+impl Drop for XCIpcGuard {
+    fn drop(&mut self) {
+        self.accept.abort();
+        if let Ok(mut history) = self.history.lock() {
+            history.sender = None;
+        }
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+/// Publish [`XCEvent`]s of a build as newline-delimited JSON over a local socket: a Unix domain
+/// socket, or a named pipe on windows.
+///
+/// Clients joining late first receive the last 10,000 events published so far, and may send
+/// [`XCIpcRequest`] lines to control the build.
+pub struct XCIpcServer {
+    path: PathBuf,
+}
+
+impl XCIpcServer {
+    /// Create server listening on given socket path
+    pub fn new<P: AsRef<Path>>(path: P) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+        }
+    }
+
+    /// Spawn xcodebuild through given logger and publish its events while streaming them.
+    ///
+    /// The socket is removed once the build stream ends or is dropped; connected clients receive
+    /// the remaining events before their connection is closed.
+    pub fn spawn_and_stream(
+        self,
+        logger: &mut XCLogger,
+    ) -> Result<Pin<Box<dyn Stream<Item = XCEvent> + Send>>> {
+        let mut incoming = Endpoint::new(self.path.to_string_lossy().to_string())
+            .incoming()
+            .with_context(|| format!("Unable to listen on {:?}", self.path))?
+            .boxed();
+        let mut events = logger.spawn_and_stream_events()?;
+        let cancel = logger.cancel_handle();
+        let (sender, _) = broadcast::channel(CLIENT_CAPACITY);
+        let history = Arc::new(Mutex::new(XCIpcHistory {
+            lines: VecDeque::new(),
+            sender: Some(sender),
+        }));
+
+        let guard = {
+            let clients = history.clone();
+            let accept = tokio::spawn(async move {
+                while let Some(connection) = incoming.next().await {
+                    match connection {
+                        Ok(connection) => {
+                            tokio::spawn(serve(connection, clients.clone(), cancel.clone()));
+                        }
+                        Err(e) => tracing::error!("Unable to accept client: {e}"),
+                    }
+                }
+            });
+            XCIpcGuard {
+                accept,
+                history: history.clone(),
+                path: self.path,
+            }
+        };
+
+        Ok(stream! {
+            while let Some(event) = events.next().await {
+                match serde_json::to_string(&event) {
+                    Ok(line) => {
+                        let mut history = history.lock().unwrap();
+                        if let Some(ref sender) = history.sender {
+                            let _ = sender.send(line.clone());
+                        }
+                        if history.lines.len() == HISTORY_CAPACITY {
+                            history.lines.pop_front();
+                        }
+                        history.lines.push_back(line);
+                    }
+                    Err(e) => tracing::error!("Unable to serialize event: {e}"),
+                };
+                yield event
+            }
+            drop(guard);
+        }
+        .boxed())
+    }
+}
+
+/// Replay history to a client, then forward new events while handling its requests.
+async fn serve<C>(connection: C, history: Arc<Mutex<XCIpcHistory>>, cancel: XCCancelHandle)
+where
+    C: AsyncRead + AsyncWrite + Send,
+{
+    let (reader, mut writer) = tokio::io::split(connection);
+    let mut requests = BufReader::new(reader).lines();
+
+    let (lines, mut receiver) = {
+        let history = history.lock().unwrap();
+        (
+            history.lines.clone(),
+            history.sender.as_ref().map(|s| s.subscribe()),
+        )
+    };
+    for line in lines {
+        if write_line(&mut writer, &line).await.is_err() {
+            return;
+        }
+    }
+
+    while let Some(ref mut events) = receiver {
+        tokio::select! {
+            event = events.recv() => match event {
+                Ok(line) => {
+                    if write_line(&mut writer, &line).await.is_err() {
+                        return;
+                    }
+                }
+                Err(RecvError::Lagged(skipped)) => {
+                    tracing::warn!("Client lagged behind, skipped {skipped} events");
+                }
+                Err(RecvError::Closed) => receiver = None,
+            },
+            request = requests.next_line() => match request {
+                Ok(Some(line)) => match serde_json::from_str(&line) {
+                    Ok(XCIpcRequest::Cancel) => cancel.cancel(),
+                    Err(e) => tracing::warn!("Invalid request {line:?}: {e}"),
+                },
+                // Client stopped sending requests, keep publishing
+                Ok(None) | Err(_) => {
+                    let mut events = receiver.take().unwrap();
+                    loop {
+                        match events.recv().await {
+                            Ok(line) => {
+                                if write_line(&mut writer, &line).await.is_err() {
+                                    return;
+                                }
+                            }
+                            Err(RecvError::Lagged(skipped)) => {
+                                tracing::warn!("Client lagged behind, skipped {skipped} events");
+                            }
+                            Err(RecvError::Closed) => break,
+                        }
+                    }
+                }
+            },
+        }
+    }
+    let _ = writer.shutdown().await;
+}
+
+async fn write_line<W: AsyncWrite + Unpin>(writer: &mut W, line: &str) -> std::io::Result<()> {
+    writer.write_all(line.as_bytes()).await?;
+    writer.write_all(b"\n").await
+}
+
+/// Client attached to a build published by [`XCIpcServer`]
+pub struct XCIpcClient {
+    reader: Lines<BufReader<ReadHalf<parity_tokio_ipc::Connection>>>,
+    writer: WriteHalf<parity_tokio_ipc::Connection>,
+}
+
+impl XCIpcClient {
+    /// Connect to server listening on given socket path
+    pub async fn connect<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let connection = Endpoint::connect(path.as_ref())
+            .await
+            .with_context(|| format!("Unable to connect to {:?}", path.as_ref()))?;
+        let (reader, writer) = tokio::io::split(connection);
+        Ok(Self {
+            reader: BufReader::new(reader).lines(),
+            writer,
+        })
+    }
+
+    /// Get next event, starting with events published before connecting. Returns `None` once
+    /// the build ended and every event was received.
+    pub async fn next_event(&mut self) -> Result<Option<XCEvent>> {
+        match self.reader.next_line().await? {
+            Some(line) => Ok(Some(serde_json::from_str(&line)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Send a control message to the server
+    pub async fn send(&mut self, request: XCIpcRequest) -> Result<()> {
+        let line = serde_json::to_string(&request)?;
+        write_line(&mut self.writer, &line).await?;
+        Ok(())
+    }
+
+    /// Gracefully cancel the build
+    pub async fn cancel(&mut self) -> Result<()> {
+        self.send(XCIpcRequest::Cancel).await
+    }
+}
+
+#[cfg(unix)]
+#[tokio::test]
+#[tracing_test::traced_test]
+async fn test_ipc() {
+    let socket = crate::runner::temp_path("sock");
+    let script = crate::runner::StandIn::new(
+        "#!/bin/sh\n\
+        trap 'kill $pid; echo \"** BUILD INTERRUPTED **\"; exit 130' INT\n\
+        echo \"CompileSwift normal arm64 /path/to/App.swift (in target 'App' from project 'App')\"\n\
+        sleep 5 & pid=$!\n\
+        wait $pid\n",
+    );
+
+    let mut logger =
+        XCLogger::new_with_runner(&script.runner(), std::env::temp_dir(), ["build"]).unwrap();
+    let mut events = XCIpcServer::new(&socket)
+        .spawn_and_stream(&mut logger)
+        .unwrap();
+
+    // Wait for the first event, so the client joins late and gets it replayed
+    let first = events.next().await.unwrap();
+    let local = tokio::spawn(async move {
+        let mut local = vec![first];
+        local.extend(events.collect::<Vec<_>>().await);
+        local
+    });
+
+    let mut client = XCIpcClient::connect(&socket).await.unwrap();
+    let mut received = vec![client.next_event().await.unwrap().unwrap()];
+    client.cancel().await.unwrap();
+    while let Some(event) = client.next_event().await.unwrap() {
+        received.push(event);
+    }
+    let local = local.await.unwrap();
+
+    let result = match received.last() {
+        Some(XCEvent::Finished(result)) => result,
+        event => panic!("Unexpected {event:?}"),
+    };
+    assert!(received[0].is_output());
+    assert_eq!(received.len(), local.len());
+    assert!(result.outcome().is_cancelled());
+    assert_eq!(result.steps.len(), 1);
+    assert!(!socket.exists());
+    assert!(XCIpcClient::connect(&socket).await.is_err());
+}
+
+#[cfg(unix)]
+#[tokio::test]
+#[tracing_test::traced_test]
+async fn test_ipc_dropped() {
+    let socket = crate::runner::temp_path("sock");
+    let script = crate::runner::StandIn::new(
+        "#!/bin/sh\n\
+        echo \"CompileSwift normal arm64 /path/to/App.swift (in target 'App' from project 'App')\"\n\
+        sleep 1\n",
+    );
+
+    let mut logger =
+        XCLogger::new_with_runner(&script.runner(), std::env::temp_dir(), ["build"]).unwrap();
+    let mut events = XCIpcServer::new(&socket)
+        .spawn_and_stream(&mut logger)
+        .unwrap();
+    events.next().await.unwrap();
+
+    let mut client = XCIpcClient::connect(&socket).await.unwrap();
+    assert!(client.next_event().await.unwrap().unwrap().is_output());
+    drop(events);
+
+    let end = tokio::time::timeout(std::time::Duration::from_secs(5), client.next_event()).await;
+    assert!(end.unwrap().unwrap().is_none());
+    assert!(!socket.exists());
+    assert!(XCIpcClient::connect(&socket).await.is_err());
+}
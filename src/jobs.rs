@@ -0,0 +1,188 @@
+use crate::{XCBuildOutcome, XCBuildResult, XCEvent, XCLogger};
+use async_stream::stream;
+use process_stream::{Stream, StreamExt};
+use std::pin::Pin;
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+use tokio_stream::StreamMap;
+
+/// Event of a job run by [`XCJobs`]
+#[derive(Debug, Clone, derive_is_enum_variant::is_enum_variant)]
+pub enum XCJobEvent {
+    /// Event yielded by the job's [`XCLogger`]
+    Event {
+        /// Job identifier
+        id: String,
+        /// Logger event
+        event: XCEvent,
+    },
+    /// Job could not be spawned
+    Failed {
+        /// Job identifier
+        id: String,
+        /// Spawn error
+        error: String,
+    },
+    /// All jobs ended
+    Finished(XCJobsResult),
+}
+
+/// Result of a job run by [`XCJobs`]
+#[derive(Debug, Clone)]
+pub struct XCJobResult {
+    /// Job identifier
+    pub id: String,
+    /// Build result, or the error raised while spawning the job
+    pub result: Result<Box<XCBuildResult>, String>,
+}
+
+impl XCJobResult {
+    /// Get outcome of the job, if it was spawned
+    pub fn outcome(&self) -> Option<XCBuildOutcome> {
+        self.result.as_ref().ok().map(|r| r.outcome())
+    }
+
+    /// Whether the job was spawned and succeeded
+    pub fn succeeded(&self) -> bool {
+        matches!(self.outcome(), Some(XCBuildOutcome::Succeeded))
+    }
+}
+
+/// Results of all jobs run by [`XCJobs`], in the order the jobs were added
+#[derive(Debug, Clone, Default, derive_deref_rs::Deref)]
+pub struct XCJobsResult(Vec<XCJobResult>);
+
+impl XCJobsResult {
+    /// Get result of job with given identifier
+    pub fn get(&self, id: &str) -> Option<&XCJobResult> {
+        self.iter().find(|j| j.id == id)
+    }
+
+    /// Whether every job succeeded
+    pub fn succeeded(&self) -> bool {
+        self.iter().all(XCJobResult::succeeded)
+    }
+
+    /// Get jobs that did not succeed
+    pub fn failed(&self) -> impl Iterator<Item = &XCJobResult> {
+        self.iter().filter(|j| !j.succeeded())
+    }
+}
+
+/// Run several [`XCLogger`] builds with a concurrency limit, merging their events
+pub struct XCJobs {
+    jobs: Vec<(String, XCLogger)>,
+    concurrency: usize,
+}
+
+impl XCJobs {
+    /// Create orchestrator running at most `concurrency` jobs at once
+    pub fn new(concurrency: usize) -> Self {
+        Self {
+            jobs: vec![],
+            concurrency: concurrency.max(1),
+        }
+    }
+
+    /// Add job with given identifier
+    pub fn add<S: Into<String>>(&mut self, id: S, logger: XCLogger) {
+        self.jobs.push((id.into(), logger));
+    }
+
+    /// Spawn jobs and stream their events tagged with job identifiers, ending with
+    /// [`XCJobEvent::Finished`].
+    pub fn spawn_and_stream(self) -> Pin<Box<dyn Stream<Item = XCJobEvent> + Send>> {
+        let semaphore = Arc::new(Semaphore::new(self.concurrency));
+        let mut results = self
+            .jobs
+            .iter()
+            .map(|(id, _)| XCJobResult {
+                id: id.clone(),
+                result: Err("Job did not finish".into()),
+            })
+            .collect::<Vec<_>>();
+        let mut streams = StreamMap::new();
+
+        for (index, (id, mut logger)) in self.jobs.into_iter().enumerate() {
+            let semaphore = semaphore.clone();
+            let job = stream! {
+                let _permit = semaphore.acquire_owned().await;
+                match logger.spawn_and_stream_events() {
+                    Ok(mut events) => {
+                        while let Some(event) = events.next().await {
+                            yield XCJobEvent::Event { id: id.clone(), event }
+                        }
+                    }
+                    Err(e) => yield XCJobEvent::Failed { id, error: e.to_string() },
+                }
+            };
+            streams.insert(index, job.boxed());
+        }
+
+        stream! {
+            while let Some((index, event)) = streams.next().await {
+                match &event {
+                    XCJobEvent::Event { event: XCEvent::Finished(result), .. } => {
+                        results[index].result = Ok(result.clone());
+                    }
+                    XCJobEvent::Failed { error, .. } => {
+                        results[index].result = Err(error.clone());
+                    }
+                    _ => (),
+                }
+                yield event
+            }
+            yield XCJobEvent::Finished(XCJobsResult(results))
+        }
+        .boxed()
+    }
+}
+
+#[tokio::test]
+#[tracing_test::traced_test]
+async fn test_jobs() {
+    let mut jobs = XCJobs::new(2);
+    jobs.add(
+        "app",
+        XCLogger::from_reader(&b"** BUILD SUCCEEDED **\n"[..]),
+    );
+    jobs.add(
+        "tests",
+        XCLogger::from_reader(
+            &b"Test Case '-[AppTests.FooTests testBar]' started.
+Test Case '-[AppTests.FooTests testBar]' failed (0.012 seconds).
+** TEST FAILED **
+"[..],
+        ),
+    );
+    let missing = XCLogger::new("/path/to/missing/root", ["build"]).unwrap();
+    jobs.add("missing", missing);
+    jobs.add(
+        "clean",
+        XCLogger::from_reader(&b"** CLEAN SUCCEEDED **\n"[..]),
+    );
+
+    let events = jobs.spawn_and_stream().collect::<Vec<_>>().await;
+    let result = match events.last() {
+        Some(XCJobEvent::Finished(result)) => result,
+        event => panic!("Unexpected {event:?}"),
+    };
+
+    assert!(events.iter().any(|e| matches!(
+        e,
+        XCJobEvent::Event { id, event: XCEvent::Output(o) } if id == "tests" && o.is_error()
+    )));
+    assert_eq!(
+        result.iter().map(|j| j.id.as_str()).collect::<Vec<_>>(),
+        vec!["app", "tests", "missing", "clean"]
+    );
+    assert!(result.get("app").unwrap().succeeded());
+    assert!(result.get("clean").unwrap().succeeded());
+    assert_eq!(
+        result.get("tests").unwrap().outcome(),
+        Some(XCBuildOutcome::TestsFailed)
+    );
+    assert!(result.get("missing").unwrap().result.is_err());
+    assert!(!result.succeeded());
+    assert_eq!(result.failed().count(), 2);
+}
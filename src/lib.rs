@@ -4,11 +4,39 @@
 #![deny(rustdoc::broken_intra_doc_links)]
 #![doc = include_str!("../README.md")]
 
+mod broadcast;
+mod build_command;
 mod build_settings;
+mod cancel;
 mod compile;
+mod destination;
+mod ipc;
+mod jobs;
 mod logger;
 pub mod parser;
+mod project_info;
+mod result;
+mod retry;
+mod runner;
+mod toolchain;
+mod watch;
 
+pub use broadcast::XCBroadcast;
+pub use build_command::{XCBuildAction, XCBuildCommand};
 pub use build_settings::XCBuildSettings;
+pub use cancel::{XCCancelHandle, XCCancelReason};
 pub use compile::{XCCompilationDatabase, XCCompileArgs, XCCompileCommand};
-pub use logger::XCLogger;
+pub use destination::XCDestination;
+pub use ipc::{XCIpcClient, XCIpcRequest, XCIpcServer};
+pub use jobs::{XCJobEvent, XCJobResult, XCJobs, XCJobsResult};
+pub use logger::{XCEvent, XCLogger};
+pub use project_info::XCProjectInfo;
+pub use result::{
+    XCAction, XCActionOutcome, XCActionResult, XCBuildOutcome, XCBuildResult, XCDiagnosticSeverity,
+    XCFailedCommand, XCInvocationError, XCLintDiagnostic, XCScriptDiagnostic, XCScriptOutput,
+    XCSlowTypeCheck, XCStep, XCTaskTiming, XCTestCase, XCTestCrash, XCTestResults, XCTestStatus,
+};
+pub use retry::{XCRetryEvent, XCRetryReport, XCTestRetry};
+pub use runner::XCRunner;
+pub use toolchain::{XCSdk, XCVersion};
+pub use watch::{XCWatch, XCWatchEvent};
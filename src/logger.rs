@@ -1,15 +1,34 @@
-use crate::parser::{parse, XCOutput, XCOutputTask, XCLOG_MATCHER};
-use crate::XCCompileCommand;
+use crate::parser::XCLOG_MATCHER;
+use crate::parser::{parse_with_state, split_timestamp, strip_ansi};
+use crate::parser::{XCOutput, XCOutputTask, XCParseState};
+use crate::{XCBuildResult, XCCancelHandle, XCCancelReason, XCCompileCommand, XCRunner};
 use anyhow::Result;
 use async_stream::stream;
-use process_stream::{ProcessExt, ProcessItem, Stream, StreamExt};
+use process_stream::{into_stream, ProcessExt, ProcessItem, Stream, StreamExt};
+use serde::{Deserialize, Serialize};
 use std::ffi;
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Duration;
 use std::{path::Path, pin::Pin};
+use time::{format_description::well_known::Rfc3339, OffsetDateTime};
+use tokio::io::{AsyncRead, AsyncWrite, AsyncWriteExt};
 use tokio::process::Command;
 use tokio::sync::Mutex;
 use tokio::sync::Notify;
+use tokio::time::Instant;
+
+/// Time to wait after SIGINT before killing xcodebuild
+const DEFAULT_GRACE_PERIOD: Duration = Duration::from_secs(10);
+
+/// Event yielded by [`XCLogger::spawn_and_stream_events`]
+#[derive(Debug, Clone, Serialize, Deserialize, derive_is_enum_variant::is_enum_variant)]
+pub enum XCEvent {
+    /// Formatted output of a log line
+    Output(XCOutput),
+    /// Structured result, yielded once the process exits
+    Finished(Box<XCBuildResult>),
+}
 
 /// XCLogger struct
 #[derive(derive_deref_rs::Deref)]
@@ -19,8 +38,28 @@ pub struct XCLogger {
     #[deref]
     inner: tokio::process::Command,
     abort: Option<Arc<Notify>>,
+    /// Saved log read instead of spawning xcodebuild
+    replay: Option<Pin<Box<dyn AsyncRead + Send + Sync>>>,
+    /// Writer receiving raw output lines and whether to prefix them with timestamps
+    log: Option<(Pin<Box<dyn AsyncWrite + Send + Sync>>, bool)>,
+    /// Path of the file receiving raw output lines
+    log_path: Option<PathBuf>,
+    /// Handle used to gracefully cancel the build
+    cancel: XCCancelHandle,
+    /// Reason the build was cancelled, set by the spawned process stream
+    cancel_reason: Arc<Mutex<Option<XCCancelReason>>>,
+    /// Signal that terminated xcodebuild, set by the spawned process stream
+    signal: Arc<Mutex<Option<i32>>>,
+    /// Time to wait after SIGINT before killing xcodebuild
+    grace_period: Duration,
+    /// Overall build timeout
+    timeout: Option<Duration>,
+    /// Maximum time without output
+    idle_timeout: Option<Duration>,
     /// Arc Reference to compile_commands
     pub compile_commands: Arc<Mutex<Vec<XCCompileCommand>>>,
+    /// Arc Reference to structured results, updated once the stream ends
+    pub result: Arc<Mutex<XCBuildResult>>,
 }
 
 impl ProcessExt for XCLogger {
@@ -31,8 +70,7 @@ impl ProcessExt for XCLogger {
     fn spawn_and_stream(
         &mut self,
     ) -> std::io::Result<Pin<Box<dyn Stream<Item = ProcessItem> + Send>>> {
-        let process_stream = self._spawn_and_stream()?;
-        let mut output_stream = self.process_stream(process_stream);
+        let mut output_stream = self.spawn_and_stream_outputs()?;
 
         Ok(stream! {
             while let Some(output) = output_stream.next().await {
@@ -68,33 +106,250 @@ impl XCLogger {
         I: IntoIterator<Item = S> + Send,
         S: AsRef<ffi::OsStr> + Send,
     {
-        let mut inner = Command::new("/usr/bin/xcodebuild");
+        Self::new_with_runner(&XCRunner::default(), root, args)
+    }
+
+    /// Create new XCLogger instance running xcodebuild configured by [`XCRunner`] in given root
+    /// with given build arguments.
+    pub fn new_with_runner<P, I, S>(runner: &XCRunner, root: P, args: I) -> Result<Self>
+    where
+        P: AsRef<Path> + Send,
+        I: IntoIterator<Item = S> + Send,
+        S: AsRef<ffi::OsStr> + Send,
+    {
+        let mut inner = Command::from(runner.command(&root));
 
-        inner.current_dir(&root);
         inner.args(args);
 
         Ok(Self {
             root: root.as_ref().to_path_buf(),
             inner,
             abort: None,
+            replay: None,
+            log: None,
+            log_path: None,
+            cancel: Default::default(),
+            cancel_reason: Default::default(),
+            signal: Default::default(),
+            grace_period: DEFAULT_GRACE_PERIOD,
+            timeout: None,
+            idle_timeout: None,
             compile_commands: Default::default(),
+            result: Default::default(),
         })
     }
 
+    /// Create new XCLogger instance replaying xcodebuild output read from given reader, e.g.
+    /// `tokio::io::stdin()`, instead of running xcodebuild.
+    pub fn from_reader<R>(reader: R) -> Self
+    where
+        R: AsyncRead + Send + Sync + 'static,
+    {
+        let root = PathBuf::default();
+        Self {
+            inner: Command::from(XCRunner::default().command(&root)),
+            root,
+            abort: None,
+            replay: Some(Box::pin(reader)),
+            log: None,
+            log_path: None,
+            cancel: Default::default(),
+            cancel_reason: Default::default(),
+            signal: Default::default(),
+            grace_period: DEFAULT_GRACE_PERIOD,
+            timeout: None,
+            idle_timeout: None,
+            compile_commands: Default::default(),
+            result: Default::default(),
+        }
+    }
+
+    /// Create new XCLogger instance replaying xcodebuild output saved at given path.
+    pub async fn from_path<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let file = tokio::fs::File::open(path).await?;
+        Ok(Self::from_reader(file))
+    }
+
+    /// Write every raw output line to given writer, optionally prefixed with RFC 3339 timestamps.
+    pub fn log_to_writer<W>(&mut self, writer: W, timestamps: bool)
+    where
+        W: AsyncWrite + Send + Sync + 'static,
+    {
+        self.log = Some((Box::pin(writer), timestamps));
+    }
+
+    /// Write every raw output line to file at given path, optionally prefixed with RFC 3339
+    /// timestamps.
+    pub async fn log_to_path<P: AsRef<Path>>(&mut self, path: P, timestamps: bool) -> Result<()> {
+        let file = tokio::fs::File::create(&path).await?;
+        self.log_to_writer(file, timestamps);
+        self.log_path = Some(path.as_ref().to_path_buf());
+        Ok(())
+    }
+
+    /// Get handle to gracefully cancel the build.
+    pub fn cancel_handle(&self) -> XCCancelHandle {
+        self.cancel.clone()
+    }
+
+    /// Set time to wait after SIGINT before killing xcodebuild, 10 seconds by default.
+    pub fn set_grace_period(&mut self, grace_period: Duration) {
+        self.grace_period = grace_period;
+    }
+
+    /// Gracefully cancel the build once it runs longer than given duration.
+    pub fn set_timeout(&mut self, timeout: Duration) {
+        self.timeout = Some(timeout);
+    }
+
+    /// Gracefully cancel the build once it prints no output for longer than given duration.
+    pub fn set_idle_timeout(&mut self, idle_timeout: Duration) {
+        self.idle_timeout = Some(idle_timeout);
+    }
+
+    /// Spawn xcodebuild and stream formatted outputs, keeping their [`XCOutputTask`] kind.
+    pub fn spawn_and_stream_outputs(
+        &mut self,
+    ) -> std::io::Result<Pin<Box<dyn Stream<Item = XCOutput> + Send>>> {
+        let process_stream = self.spawn_or_replay()?;
+        Ok(self.process_stream(process_stream))
+    }
+
+    /// Spawn xcodebuild and stream outputs, ending with [`XCEvent::Finished`].
+    pub fn spawn_and_stream_events(
+        &mut self,
+    ) -> std::io::Result<Pin<Box<dyn Stream<Item = XCEvent> + Send>>> {
+        let process_stream = self.spawn_or_replay()?;
+        Ok(self.process_events(process_stream))
+    }
+
+    /// Stream lines of the replayed log if any, otherwise spawn xcodebuild.
+    fn spawn_or_replay(
+        &mut self,
+    ) -> std::io::Result<Pin<Box<dyn Stream<Item = ProcessItem> + Send>>> {
+        let output_stream = match self.replay.take() {
+            Some(reader) => into_stream(reader, true).boxed(),
+            None => self.spawn()?,
+        };
+
+        Ok(match self.log.take() {
+            Some((writer, timestamps)) => tee(output_stream, writer, timestamps),
+            None => output_stream,
+        })
+    }
+
+    /// Spawn xcodebuild, yielding its exit code only once stdout and stderr are fully read.
+    ///
+    /// On cancellation, timeout or idle timeout xcodebuild gets SIGINT, and SIGKILL once the
+    /// grace period ends. The aborter kills it right away.
+    fn spawn(&mut self) -> std::io::Result<Pin<Box<dyn Stream<Item = ProcessItem> + Send>>> {
+        let abort = Arc::new(Notify::new());
+        let mut child = self.command().spawn()?;
+        let stdout = into_stream(child.stdout.take().unwrap(), true);
+        let stderr = into_stream(child.stderr.take().unwrap(), false);
+        let mut std_stream = tokio_stream::StreamExt::merge(stdout, stderr);
+
+        self.set_aborter(Some(abort.clone()));
+
+        let cancel = self.cancel.clone();
+        let cancel_reason = self.cancel_reason.clone();
+        let signal = self.signal.clone();
+        let grace_period = self.grace_period;
+        let idle_timeout = self.idle_timeout;
+        let deadline = self.timeout.map(|t| Instant::now() + t);
+
+        Ok(stream! {
+            let mut last_output = Instant::now();
+            let mut kill_at = None;
+            loop {
+                let reason = tokio::select! {
+                    output = std_stream.next() => match output {
+                        Some(output) => {
+                            last_output = Instant::now();
+                            yield output;
+                            continue
+                        }
+                        None => break,
+                    },
+                    _ = abort.notified() => {
+                        *cancel_reason.lock().await = Some(XCCancelReason::Aborted);
+                        if let Err(err) = child.start_kill() {
+                            yield ProcessItem::Error(format!("abort Process Error: {err}"));
+                        }
+                        break;
+                    }
+                    _ = sleep_until(kill_at) => {
+                        if let Err(err) = child.start_kill() {
+                            yield ProcessItem::Error(format!("abort Process Error: {err}"));
+                        }
+                        break;
+                    }
+                    _ = cancel.cancelled(), if kill_at.is_none() => XCCancelReason::Cancelled,
+                    _ = sleep_until(deadline), if kill_at.is_none() => XCCancelReason::Timeout,
+                    _ = sleep_until(idle_timeout.map(|t| last_output + t)), if kill_at.is_none() => {
+                        XCCancelReason::Idle
+                    }
+                };
+
+                *cancel_reason.lock().await = Some(reason);
+                kill_at = Some(Instant::now() + grace_period);
+                if let Err(err) = interrupt(&mut child) {
+                    yield ProcessItem::Error(format!("abort Process Error: {err}"));
+                }
+            }
+
+            match child.wait().await {
+                Ok(status) => match status.code() {
+                    Some(code) => yield ProcessItem::Exit(format!("{code}")),
+                    None => match exit_signal(&status) {
+                        Some(code) => {
+                            *signal.lock().await = Some(code);
+                            yield ProcessItem::Error(format!("Terminated by signal {code}"))
+                        }
+                        None => yield ProcessItem::Error("Unable to get exit code".into()),
+                    },
+                },
+                Err(err) => yield ProcessItem::Error(err.to_string()),
+            }
+        }
+        .boxed())
+    }
+
+    pub(crate) fn process_events(
+        &self,
+        output_stream: Pin<Box<dyn Stream<Item = ProcessItem> + Send>>,
+    ) -> Pin<Box<dyn Stream<Item = XCEvent> + Send>> {
+        let mut output_stream = self.process_stream(output_stream);
+        let result = self.result.clone();
+
+        stream! {
+            while let Some(output) = output_stream.next().await {
+                yield XCEvent::Output(output)
+            }
+            yield XCEvent::Finished(Box::new(result.lock().await.clone()))
+        }
+        .boxed()
+    }
+
     pub(crate) fn process_stream(
         &self,
         mut output_stream: Pin<Box<dyn Stream<Item = ProcessItem> + Send>>,
     ) -> Pin<Box<dyn Stream<Item = XCOutput> + Send>> {
         let compile_commands = self.compile_commands.clone();
+        let result = self.result.clone();
+        let log_path = self.log_path.clone();
+        let cancel_reason = self.cancel_reason.clone();
+        let signal = self.signal.clone();
 
         stream! {
             let mut compile_commands = compile_commands.lock().await;
+            let mut state = XCParseState::default();
             while let Some(output) = output_stream.next().await {
 
                 // Try to process compile command first
                 if let ProcessItem::Output(line) = &output {
                     if let Some(cmd) = XCLOG_MATCHER
-                        .get_compile_command(line.as_str())
+                        .get_compile_command(&strip_ansi(split_timestamp(line).map_or(line, |(_, l)| l)))
                         .and_then(XCCompileCommand::from_compile_command_data)
                     {
                         compile_commands.push(cmd);
@@ -104,7 +359,7 @@ impl XCLogger {
 
                 match output {
                     ProcessItem::Error(line) => {
-                        match parse(line, &mut output_stream).await {
+                        match parse_with_state(line, &mut output_stream, &mut state).await {
                             Ok(Some(lines)) => {
                                 for output in lines.into_iter() {
                                     yield output
@@ -114,7 +369,7 @@ impl XCLogger {
                         }
                     },
                     ProcessItem::Output(line) => {
-                        match parse(line, &mut output_stream).await {
+                        match parse_with_state(line, &mut output_stream, &mut state).await {
                             Ok(Some(outputs)) => {
                                 for output in outputs.into_iter() {
                                     yield output
@@ -124,34 +379,89 @@ impl XCLogger {
                         }
 
                     },
-                    ProcessItem::Exit(exit) => {
-                        let value = exit.trim();
-                        yield XCOutput {
-                            kind: XCOutputTask::Exit,
-                            value: value.into()
-                        };
-                    }
+                    ProcessItem::Exit(exit) => yield state.observe_exit(&exit),
                 }
             }
+
+            state.result.signal = *signal.lock().await;
+            if let Some(reason) = *cancel_reason.lock().await {
+                state.result.cancelled = Some(reason);
+                yield XCOutput::new(XCOutputTask::Error, format!("[Error] [Cancelled] {reason}"));
+            }
+
+            state.finish();
+            state.result.compile_commands = compile_commands.clone();
+            state.result.log_path = log_path;
+            *result.lock().await = state.result;
         }
         .boxed()
     }
 }
 
+/// Sleep until given deadline, or forever if there is none.
+async fn sleep_until(deadline: Option<Instant>) {
+    match deadline {
+        Some(deadline) => tokio::time::sleep_until(deadline).await,
+        None => std::future::pending().await,
+    }
+}
+
+/// Send SIGINT to child process, killing it where signals are unsupported.
+fn interrupt(child: &mut tokio::process::Child) -> std::io::Result<()> {
+    #[cfg(unix)]
+    if let Some(pid) = child.id() {
+        return match unsafe { libc::kill(pid as libc::pid_t, libc::SIGINT) } {
+            0 => Ok(()),
+            _ => Err(std::io::Error::last_os_error()),
+        };
+    }
+    child.start_kill()
+}
+
+/// Get signal that terminated the process, if any.
+fn exit_signal(status: &std::process::ExitStatus) -> Option<i32> {
+    #[cfg(unix)]
+    return std::os::unix::process::ExitStatusExt::signal(status);
+    #[cfg(not(unix))]
+    return None;
+}
+
+/// Write raw output lines of given stream to writer as they pass through.
+fn tee(
+    mut output_stream: Pin<Box<dyn Stream<Item = ProcessItem> + Send>>,
+    mut writer: Pin<Box<dyn AsyncWrite + Send + Sync>>,
+    timestamps: bool,
+) -> Pin<Box<dyn Stream<Item = ProcessItem> + Send>> {
+    stream! {
+        while let Some(output) = output_stream.next().await {
+            if let ProcessItem::Output(line) | ProcessItem::Error(line) = &output {
+                let line = match timestamps {
+                    true => match OffsetDateTime::now_utc().format(&Rfc3339) {
+                        Ok(timestamp) => format!("{timestamp} {line}\n"),
+                        Err(_) => format!("{line}\n"),
+                    },
+                    false => format!("{line}\n"),
+                };
+                if let Err(e) = writer.write_all(line.as_bytes()).await {
+                    tracing::error!("LogError: {e}");
+                }
+            }
+            yield output
+        }
+        if let Err(e) = writer.flush().await {
+            tracing::error!("LogError: {e}");
+        }
+    }
+    .boxed()
+}
+
 #[tokio::test]
 #[tracing_test::traced_test]
 async fn case_d() {
-    let logger = XCLogger::new("", [""]).expect("Create logger");
-    let stream = stream! {
-        let content = include_str!("../tests/case_d.log");
-        let lines = content.split("\n");
-        for line in lines {
-            yield ProcessItem::Output(line.to_string())
-        }
-    }
-    .boxed();
+    let mut logger = XCLogger::from_reader(&include_bytes!("../tests/case_d.log")[..]);
     let error_outputs = logger
-        .process_stream(stream)
+        .spawn_and_stream_outputs()
+        .unwrap()
         .collect::<Vec<_>>()
         .await
         .into_iter()
@@ -159,3 +469,268 @@ async fn case_d() {
         .collect::<Vec<_>>();
     assert_eq!(error_outputs.len(), 0, "{error_outputs:#?}")
 }
+
+#[tokio::test]
+#[tracing_test::traced_test]
+async fn events() {
+    let logger = XCLogger::new("", [""]).expect("Create logger");
+    let stream = stream! {
+        let content = "/path/to/App/AppDelegate.swift:10:5: warning: unused variable 'x'\n\
+            \n\
+            ** BUILD FAILED **";
+        for line in content.split('\n') {
+            yield ProcessItem::Output(line.to_string())
+        }
+        yield ProcessItem::Exit("65".into())
+    }
+    .boxed();
+    let events = logger.process_events(stream).collect::<Vec<_>>().await;
+
+    match events.last() {
+        Some(XCEvent::Finished(result)) => {
+            assert_eq!(result.exit_code, Some(65));
+            assert_eq!(result.warnings_count, 1);
+            assert_eq!(result.errors_count, 0);
+        }
+        event => panic!("Unexpected {event:?}"),
+    }
+    assert!(events[..events.len() - 1].iter().all(XCEvent::is_output));
+}
+
+#[tokio::test]
+#[tracing_test::traced_test]
+async fn error_count() {
+    let logger = XCLogger::new("", [""]).expect("Create logger");
+    let stream = stream! {
+        let content = "CompileSwift normal arm64 /path/to/A.swift (in target 'App' from project 'App')\n\
+            /path/to/A.swift:1:1: error: cannot find 'foo' in scope\n\
+            \n\
+            ** BUILD FAILED **\n\
+            \n\
+            The following build commands failed:\n\
+            \tCompileSwift normal arm64 /path/to/A.swift (in target 'App' from project 'App')\n\
+            (1 failure)";
+        for line in content.split('\n') {
+            yield ProcessItem::Output(line.to_string())
+        }
+        yield ProcessItem::Exit("65".into())
+    }
+    .boxed();
+    let events = logger.process_events(stream).collect::<Vec<_>>().await;
+
+    match events.last() {
+        Some(XCEvent::Finished(result)) => {
+            assert_eq!(result.errors_count, 1);
+            assert_eq!(result.failed_commands.len(), 1);
+        }
+        event => panic!("Unexpected {event:?}"),
+    }
+}
+
+#[tokio::test]
+#[tracing_test::traced_test]
+async fn exit_after_compile_error() {
+    let logger = XCLogger::new("", [""]).expect("Create logger");
+    let stream = stream! {
+        yield ProcessItem::Output("/path/to/A.swift:1:1: error: bad".into());
+        yield ProcessItem::Exit("65".into())
+    }
+    .boxed();
+    let events = logger.process_events(stream).collect::<Vec<_>>().await;
+
+    match events.last() {
+        Some(XCEvent::Finished(result)) => {
+            assert_eq!(result.exit_code, Some(65));
+            assert_eq!(result.errors_count, 1);
+            assert_eq!(result.outcome(), crate::XCBuildOutcome::BuildFailed);
+        }
+        event => panic!("Unexpected {event:?}"),
+    }
+    let outputs = events
+        .iter()
+        .filter_map(|event| match event {
+            XCEvent::Output(output) => Some(output),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    assert!(outputs.iter().all(|o| o.value != "[Error] 65"));
+    assert!(outputs.last().unwrap().kind.is_exit());
+}
+
+#[cfg(unix)]
+#[tokio::test]
+#[tracing_test::traced_test]
+async fn stand_in_runner() {
+    let script = crate::runner::StandIn::new(
+        "#!/bin/sh\n\
+        echo \"xcodebuild: error: Unknown build action '$1'.\"\n\
+        exit 64\n",
+    );
+    let runner = script
+        .runner()
+        .developer_dir("/Applications/Xcode.app/Contents/Developer");
+    let mut logger =
+        XCLogger::new_with_runner(&runner, std::env::temp_dir(), ["bulid"]).expect("Create logger");
+    let events = logger
+        .spawn_and_stream_events()
+        .unwrap()
+        .collect::<Vec<_>>()
+        .await;
+
+    match events.last() {
+        Some(XCEvent::Finished(result)) => {
+            assert_eq!(result.exit_code, Some(64));
+            assert_eq!(
+                result.invocation_errors,
+                vec![crate::XCInvocationError::UnknownAction {
+                    action: "bulid".into()
+                }]
+            );
+        }
+        event => panic!("Unexpected {event:?}"),
+    }
+}
+
+#[tokio::test]
+#[tracing_test::traced_test]
+async fn replay() {
+    let mut logger = XCLogger::from_path("tests/case_a.log").await.unwrap();
+    let outputs = logger.spawn_and_stream().unwrap().collect::<Vec<_>>().await;
+
+    assert!(!outputs.is_empty());
+    assert_eq!(logger.compile_commands.lock().await.len(), 3);
+    assert_eq!(logger.result.lock().await.compile_commands.len(), 3);
+
+    let mut logger = XCLogger::from_reader(&b"** BUILD SUCCEEDED **\n"[..]);
+    let outputs = logger
+        .spawn_and_stream_outputs()
+        .unwrap()
+        .collect::<Vec<_>>()
+        .await;
+
+    assert_eq!(outputs[0].value, "[BUILD] Succeeded");
+    assert_eq!(logger.result.lock().await.exit_code, None);
+}
+
+#[tokio::test]
+#[tracing_test::traced_test]
+async fn log_to_path() {
+    let path = crate::runner::temp_path("log");
+    let mut logger = XCLogger::from_reader(&b"Unmatched line\n** BUILD SUCCEEDED **\n"[..]);
+    logger.log_to_path(&path, true).await.unwrap();
+    let outputs = logger
+        .spawn_and_stream_outputs()
+        .unwrap()
+        .collect::<Vec<_>>()
+        .await;
+    let content = std::fs::read_to_string(&path).unwrap();
+    let lines = content.lines().collect::<Vec<_>>();
+
+    assert_eq!(outputs.len(), 1);
+    assert_eq!(lines.len(), 2);
+    assert!(split_timestamp(lines[0]).is_some());
+    assert!(lines[0].ends_with(" Unmatched line"));
+    assert_eq!(logger.result.lock().await.log_path.as_ref(), Some(&path));
+    std::fs::remove_file(path).unwrap();
+}
+
+#[cfg(unix)]
+#[tokio::test]
+#[tracing_test::traced_test]
+async fn cancellation() {
+    let script = crate::runner::StandIn::new(
+        "#!/bin/sh\n\
+        [ \"$1\" = ignore ] && trap '' INT || trap 'kill $pid; echo \"** BUILD INTERRUPTED **\"; exit 130' INT\n\
+        echo started\n\
+        sleep 5 & pid=$!\n\
+        wait $pid\n",
+    );
+    let runner = script.runner();
+
+    let mut logger = XCLogger::new_with_runner(&runner, std::env::temp_dir(), ["trap"]).unwrap();
+    logger.set_idle_timeout(Duration::from_millis(200));
+    let outputs = logger
+        .spawn_and_stream_outputs()
+        .unwrap()
+        .collect::<Vec<_>>()
+        .await;
+    let result = logger.result.lock().await;
+
+    assert_eq!(result.cancelled, Some(XCCancelReason::Idle));
+    assert_eq!(result.exit_code, Some(130));
+    assert_eq!(
+        outputs.last().unwrap().value,
+        "[Error] [Cancelled] No output"
+    );
+    drop(result);
+
+    let mut logger = XCLogger::new_with_runner(&runner, std::env::temp_dir(), ["ignore"]).unwrap();
+    logger.set_timeout(Duration::from_millis(200));
+    logger.set_grace_period(Duration::from_millis(200));
+    logger.spawn_and_stream().unwrap().collect::<Vec<_>>().await;
+    let result = logger.result.lock().await;
+
+    assert_eq!(result.cancelled, Some(XCCancelReason::Timeout));
+    assert_eq!(result.exit_code, None);
+    assert_eq!(result.signal, Some(libc::SIGKILL));
+    assert!(result.outcome().is_cancelled());
+    drop(result);
+
+    let mut logger = XCLogger::new_with_runner(&runner, std::env::temp_dir(), ["trap"]).unwrap();
+    logger.cancel_handle().cancel();
+    logger.spawn_and_stream().unwrap().collect::<Vec<_>>().await;
+
+    assert!(logger.result.lock().await.cancelled.unwrap().is_cancelled());
+
+    let mut logger = XCLogger::new_with_runner(&runner, std::env::temp_dir(), ["trap"]).unwrap();
+    let stream = logger.spawn_and_stream().unwrap();
+    logger.aborter().unwrap().notify_one();
+    stream.collect::<Vec<_>>().await;
+    let result = logger.result.lock().await;
+
+    assert_eq!(result.cancelled, Some(XCCancelReason::Aborted));
+    assert_eq!(result.exit_code, None);
+    assert_eq!(result.signal, Some(libc::SIGKILL));
+    assert!(result.outcome().is_cancelled());
+}
+
+#[tokio::test]
+#[tracing_test::traced_test]
+async fn outcomes() {
+    use crate::XCBuildOutcome::*;
+
+    let cases = [
+        ("** BUILD SUCCEEDED **", Succeeded),
+        (
+            "Test Case '-[AppTests.FooTests testBar]' started.\n\
+            Test Case '-[AppTests.FooTests testBar]' failed (0.012 seconds).\n\
+            ** TEST FAILED **",
+            TestsFailed,
+        ),
+        (
+            "Test case 'FooTests.testBar()' failed on 'Clone 1 of iPhone 14 - App (12345)' (0.010 seconds)\n\
+            ** TEST FAILED **",
+            TestsFailed,
+        ),
+        (
+            "Testing failed:\n\
+            \tApp encountered an error (Early unexpected exit, operation never finished bootstrapping)\n\
+            ** TEST FAILED **",
+            TestsNotRun,
+        ),
+        (
+            "The following build commands failed:\n\
+            \tCompileSwift normal arm64 /path/to/App/AppDelegate.swift (in target 'App' from project 'App')\n\
+            (1 failure)\n\
+            ** TEST FAILED **",
+            BuildFailed,
+        ),
+        ("Unmatched line", Unknown),
+    ];
+
+    for (content, outcome) in cases {
+        let mut logger = XCLogger::from_reader(content.as_bytes());
+        logger.spawn_and_stream().unwrap().collect::<Vec<_>>().await;
+        assert_eq!(logger.result.lock().await.outcome(), outcome, "{content}");
+    }
+}
@@ -386,6 +386,42 @@ define! [
     tests: {}
 
 },
+{
+    ident: BuildTimingSummary,
+    kind: Task,
+    desc: r"Build Timing Summary header printed with -showBuildTimingSummary",
+    captures: [ ],
+    format: "Build Timing Summary",
+    pattern: r"^Build Timing Summary$",
+    tests: {}
+},
+{
+    ident: BuildTimingSummaryItem,
+    kind: Result,
+    desc: r"Build Timing Summary task type",
+    captures: [ task, count, seconds ],
+    format: "[Timing] {task} ({count}): {seconds}s",
+    pattern: r"^(?P<task>\S.*?)\s\((?P<count>\d+)\stasks?\)\s\|\s(?P<seconds>\d+(?:\.\d+)?)\sseconds$",
+    tests: {
+        "CompileSwiftSources (4 tasks) | 12.345 seconds" =>
+            |captures| {
+                assert_eq!("CompileSwiftSources", &captures["task"]);
+                assert_eq!("4", &captures["count"]);
+                assert_eq!("12.345", &captures["seconds"]);
+            },
+        "Ld (1 task) | 0.123 seconds" =>
+            |captures| {
+                assert_eq!("Ld", &captures["task"]);
+                assert_eq!("1", &captures["count"]);
+                assert_eq!("0.123", &captures["seconds"]);
+            },
+        "Copy Swift standard libraries (2 tasks) | 1.5 seconds" =>
+            |captures| {
+                assert_eq!("Copy Swift standard libraries", &captures["task"]);
+                assert_eq!("2", &captures["count"]);
+            }
+    }
+},
 // - TESTING ----------------------------------------------------------------------
 {
     ident: TestExecuted,
@@ -546,6 +582,22 @@ define! [
             }
     }
 },
+{
+    ident: TestCaseFailed,
+    kind: Error,
+    desc: r"Test Case Failed",
+    captures: [ suite, case, time ],
+    format: "",
+    pattern: r"\s*Test Case\s'-\[(?P<suite>.*)\s(?P<case>.*)\]'\sfailed\s\((?P<time>\d*\.\d{3})\sseconds\).",
+    tests: {
+        "Test Case '-[AppTests.FooTests testBar]' failed (0.012 seconds)." =>
+            |captures| {
+                assert_eq!("AppTests.FooTests", &captures["suite"]);
+                assert_eq!("testBar", &captures["case"]);
+                assert_eq!("0.012", &captures["time"]);
+            }
+    }
+},
 {
     ident: KiwiTestCasePending,
     kind: Test,
@@ -780,10 +832,26 @@ define! [
     ident: RestartingTests,
     kind: Test,
     desc: r"Test restarting",
-    captures: [],
+    captures: [ suite, case ],
     format: "",
-    pattern:r"Restarting after unexpected exit.+$",
-    tests: {}
+    pattern: r"(?x)Restarting\safter\sunexpected\sexit
+        (?:.*?\sin\s(?P<suite>[^\s;]+)\.(?P<case>[^\s.;(]+)\(\))?.*$",
+    tests: {
+        "Restarting after unexpected exit, crash, or test timeout in FooTests.testCrash(); summary will include totals from previous launches." =>
+            |captures| {
+                assert_eq!("FooTests", &captures["suite"]);
+                assert_eq!("testCrash", &captures["case"]);
+            },
+        "Restarting after unexpected exit or crash in AppTests.FooTests.testCrash(); summary will include totals from previous launches." =>
+            |captures| {
+                assert_eq!("AppTests.FooTests", &captures["suite"]);
+                assert_eq!("testCrash", &captures["case"]);
+            },
+        "Restarting after unexpected exit, crash, or test timeout; summary will include totals from previous launches." =>
+            |captures| {
+                assert!(captures.name("suite").is_none());
+            }
+    }
 },
 {
     ident: CoverageDataGeneration,
@@ -800,8 +868,105 @@ define! [
     desc: r"Phase Success",
     captures: [ name ],
     format: "[{name}] Succeeded",
-    pattern: r"\*\*\s(P?<name>.*)\sSUCCEEDED\s\*\*",
-    tests:{}
+    pattern: r"\*\*\s(?P<name>.*)\sSUCCEEDED\s\*\*",
+    tests: {
+        "** BUILD SUCCEEDED ** [13.120 sec]" =>
+            |captures| {
+                assert_eq!("BUILD", &captures["name"]);
+            },
+        "** ARCHIVE SUCCEEDED **" =>
+            |captures| {
+                assert_eq!("ARCHIVE", &captures["name"]);
+            },
+        "** EXPORT SUCCEEDED **" =>
+            |captures| {
+                assert_eq!("EXPORT", &captures["name"]);
+            }
+    }
+},
+{
+    ident: PhaseFailed,
+    kind: Error,
+    desc: r"Phase Failed",
+    captures: [ name ],
+    format: "[{name}] Failed",
+    pattern: r"\*\*\s(?P<name>.*)\sFAILED\s\*\*",
+    tests: {
+        "** BUILD FAILED **" =>
+            |captures| {
+                assert_eq!("BUILD", &captures["name"]);
+            },
+        "** TEST FAILED **" =>
+            |captures| {
+                assert_eq!("TEST", &captures["name"]);
+            },
+        "** ARCHIVE FAILED **" =>
+            |captures| {
+                assert_eq!("ARCHIVE", &captures["name"]);
+            },
+        "** EXPORT FAILED **" =>
+            |captures| {
+                assert_eq!("EXPORT", &captures["name"]);
+            }
+    }
+},
+{
+    ident: ExportedArtifact,
+    kind: Result,
+    desc: r"Exported archive",
+    captures: [ name, path ],
+    format: "Exported {name} to {path}",
+    pattern: r"^Exported\s(?P<name>.+)\sto:\s(?P<path>.+)$",
+    tests: {
+        "Exported Example to: /path/to/Export" =>
+            |captures| {
+                assert_eq!("Example", &captures["name"]);
+                assert_eq!("/path/to/Export", &captures["path"]);
+            }
+    }
+},
+{
+    ident: ExportArchiveError,
+    kind: Error,
+    desc: r"-exportArchive error",
+    captures: [ message ],
+    format: "exportArchive: {message}",
+    pattern: r"error:\sexportArchive:?\s(?P<message>.*)$",
+    tests: {
+        "error: exportArchive: No signing certificate \"iOS Distribution\" found" =>
+            |captures| {
+                assert_eq!("No signing certificate \"iOS Distribution\" found", &captures["message"]);
+            }
+    }
+},
+{
+    ident: XcodebuildError,
+    kind: Error,
+    desc: r"xcodebuild invocation error",
+    captures: [ message ],
+    format: "{message}",
+    pattern: r"^xcodebuild:\s(?:error|fatal):\s(?P<message>.*)$",
+    tests: {
+        "xcodebuild: error: Unable to find a destination matching the provided destination specifier:" =>
+            |captures| {
+                assert_eq!("Unable to find a destination matching the provided destination specifier:", &captures["message"]);
+            },
+        "xcodebuild: error: The project named \"App\" does not contain a scheme named \"Missing\". The \"-list\" option can be used to find the names of the schemes in the project." =>
+            |captures| {
+                assert!(captures["message"].starts_with("The project named \"App\""));
+            }
+    }
+},
+{
+    ident: BuildCommandsFailed,
+    kind: Error,
+    desc: r"Header of failed build commands summary",
+    captures: [],
+    format: "The following build commands failed:",
+    pattern: r"^The following build commands failed:$",
+    tests: {
+        "The following build commands failed:" => |captures| { assert!(captures.get(0).is_some()); }
+    }
 },
 {
     ident: TestSuiteAllTestsPassed,
@@ -839,6 +1004,110 @@ define! [
             }
     }
 },
+// - Type checking ----------------------------------------------------------------
+{
+    ident: SlowTypeCheckWarning,
+    kind: Warning,
+    desc: r"-warn-long-function-bodies/-warn-long-expression-type-checking warning",
+    captures: [ filepath, line, column, symbol, ms ],
+    format: "[TypeCheck] {filepath}:{line}:{column}: {symbol} took {ms}ms",
+    pattern: r"(?x)
+        (?P<filepath>[^:]*):(?P<line>\d+):(?P<column>\d+):\swarning:\s
+        (?P<symbol>.+?)\stook\s(?P<ms>\d+(?:\.\d+)?)ms\sto\stype-check
+        (?:\s\(limit:\s\d+ms\))?$",
+    tests: {
+        "/path/to/File.swift:12:10: warning: instance method 'foo()' took 350ms to type-check (limit: 200ms)" =>
+            |captures| {
+                assert_eq!("/path/to/File.swift", &captures["filepath"]);
+                assert_eq!("12", &captures["line"]);
+                assert_eq!("10", &captures["column"]);
+                assert_eq!("instance method 'foo()'", &captures["symbol"]);
+                assert_eq!("350", &captures["ms"]);
+            },
+        "/path/to/File.swift:20:5: warning: expression took 210ms to type-check (limit: 200ms)" =>
+            |captures| {
+                assert_eq!("expression", &captures["symbol"]);
+                assert_eq!("210", &captures["ms"]);
+            }
+    }
+},
+{
+    ident: SlowTypeCheckTiming,
+    kind: Task,
+    desc: r"-debug-time-function-bodies/-debug-time-expression-type-checking timing",
+    captures: [ filepath, line, column, symbol, ms ],
+    format: "",
+    pattern: r"^\s*(?P<ms>\d+(?:\.\d+)?)ms\s+(?P<filepath>[^:\s][^:]*):(?P<line>\d+):(?P<column>\d+)\s*(?P<symbol>.*)$",
+    tests: {
+        "350.12ms\t/path/to/File.swift:12:10\tinstance method foo()" =>
+            |captures| {
+                assert_eq!("350.12", &captures["ms"]);
+                assert_eq!("/path/to/File.swift", &captures["filepath"]);
+                assert_eq!("12", &captures["line"]);
+                assert_eq!("10", &captures["column"]);
+                assert_eq!("instance method foo()", &captures["symbol"]);
+            },
+        "12.50ms\t/path/to/File.swift:5:10" =>
+            |captures| {
+                assert_eq!("12.50", &captures["ms"]);
+                assert_eq!("", &captures["symbol"]);
+            }
+    }
+},
+// - Lint -------------------------------------------------------------------------
+{
+    ident: SwiftLintDiagnostic,
+    kind: Warning,
+    desc: r"SwiftLint rule violation",
+    captures: [ location, filepath, severity, message, rule ],
+    format: "",
+    pattern: r"(?x)
+        (?P<location>(?P<filepath>[^:]*):\d+(?::\d+)?):\s
+        (?P<severity>warning|error):\s
+        (?P<message>.*?Violation:\s.*)\s
+        \((?P<rule>[\w-]+)\)$",
+    tests: {
+        "/path/to/AppDelegate.swift:10:5: warning: Line Length Violation: Line should be 120 characters or less: currently 130 characters (line_length)" =>
+            |captures| {
+                assert_eq!("/path/to/AppDelegate.swift:10:5", &captures["location"]);
+                assert_eq!("/path/to/AppDelegate.swift", &captures["filepath"]);
+                assert_eq!("warning", &captures["severity"]);
+                assert_eq!("Line Length Violation: Line should be 120 characters or less: currently 130 characters", &captures["message"]);
+                assert_eq!("line_length", &captures["rule"]);
+            },
+        "/path/to/View.swift:3:1: error: Force Cast Violation: Force casts should be avoided (force_cast)" =>
+            |captures| {
+                assert_eq!("error", &captures["severity"]);
+                assert_eq!("force_cast", &captures["rule"]);
+            }
+    }
+},
+{
+    ident: SwiftFormatDiagnostic,
+    kind: Warning,
+    desc: r"SwiftFormat lint rule violation",
+    captures: [ location, filepath, severity, message, rule ],
+    format: "",
+    pattern: r"(?x)
+        ^(?P<location>(?P<filepath>[^:]*):\d+(?::\d+)?):\s
+        (?P<severity>warning|error):\s
+        \((?P<rule>[\w-]+)\)\s
+        (?P<message>.*)$",
+    tests: {
+        "/path/to/AppDelegate.swift:12:1: warning: (indent) Indent code in accordance with the scope level." =>
+            |captures| {
+                assert_eq!("/path/to/AppDelegate.swift:12:1", &captures["location"]);
+                assert_eq!("warning", &captures["severity"]);
+                assert_eq!("indent", &captures["rule"]);
+                assert_eq!("Indent code in accordance with the scope level.", &captures["message"]);
+            },
+        "/path/to/View.swift:3:1: error: (redundantSelf) Remove explicit self where applicable." =>
+            |captures| {
+                assert_eq!("error", &captures["severity"]);
+                assert_eq!("redundantSelf", &captures["rule"]);
+            }
+    }
+},
 // - Warning ----------------------------------------------------------------------
 {
     ident: CompileWarning,
@@ -51,15 +51,25 @@ macro_rules! define
 
                 };
 
-                Ok(Some(XCOutput {
-                    value: format!("{}{}", leading, format!($format)),
-                    kind: self.kind.clone(),
-                }))
+                Ok(Some(XCOutput::new(
+                    self.kind.clone(),
+                    format!("{}{}", leading, format!($format)),
+                )))
+            }
+
+            /// Get named capture if it participated in the match
+            pub fn get(&self, capture: &str) -> Option<&'a str> {
+                self._inner.name(capture).map(|m| m.as_str())
             }
 
             #[doc = "Get data struct representation of `XC" $name "`"]
             pub fn as_data(&self) -> [<XC $name Data>] {
-                [<XC $name Data>] { $($capture: self._inner[stringify!($capture)].to_string()),* }
+                [<XC $name Data>] {
+                    $($capture: self._inner
+                        .name(stringify!($capture))
+                        .map(|m| m.as_str().to_string())
+                        .unwrap_or_default()),*
+                }
             }
         }
 
@@ -73,9 +83,15 @@ macro_rules! define
             pub fn captures<'a>(&'a self, text: &'a str) -> Option<[<XC $name Match>]<'a>> {
                 self.re.captures(text)
                     .map(|captures| {
+                        // Matchers capturing a severity report it as their kind
+                        let kind = match captures.name("severity").map(|m| m.as_str()) {
+                            Some("error") => XCOutputTask::Error,
+                            Some("warning") => XCOutputTask::Warning,
+                            _ => XCOutputTask::$kind,
+                        };
                         [<XC $name Match>] {
                             _inner: captures,
-                            kind: XCOutputTask::$kind
+                            kind
                         }
                     })
             }
@@ -94,9 +110,19 @@ macro_rules! define
             match self { $(Self::$name(v) => v.output(),)* }
         }
 
+        /// Name of the matcher, e.g. `Compile`
+        pub fn name(&self) -> &'static str {
+            match self { $(Self::$name(_) => stringify!($name),)* }
+        }
+
+        /// Get named capture if it participated in the match
+        pub fn get(&self, capture: &str) -> Option<&'a str> {
+            match self { $(Self::$name(v) => v.get(capture),)* }
+        }
+
         /// Check whether match is error
         pub fn is_error(&'a self) -> bool {
-            match self { $(Self::$name(_) => XCOutputTask::$kind.is_error(),)* }
+            match self { $(Self::$name(v) => v.kind.is_error(),)* }
         }
 
         /// Check whether match is warning
@@ -116,7 +142,7 @@ macro_rules! define
 
         /// Check whether match is warning
         pub fn is_warning(&'a self) -> bool {
-            match self { $(Self::$name(_) => XCOutputTask::$kind.is_warning(),)* }
+            match self { $(Self::$name(v) => v.kind.is_warning(),)* }
         }
 
         $(
@@ -3,32 +3,98 @@
 mod defs;
 mod gen;
 mod output;
+mod state;
 
 use anyhow::Result;
+use lazy_static::lazy_static;
 use process_stream::{ProcessItem, StreamExt};
+use regex::Regex;
+use std::borrow::Cow;
+use std::time::SystemTime;
+use time::{format_description::well_known::Rfc3339, OffsetDateTime};
 
 pub use defs::*;
 pub use output::*;
+pub use state::XCParseState;
+
+lazy_static! {
+    /// CSI sequences, including SGR colour codes
+    static ref ANSI_ESCAPE: Regex = Regex::new(r"\x1b\[[0-?]*[ -/]*[@-~]").unwrap();
+}
 
 pub(crate) type OutputStream = dyn tokio_stream::Stream<Item = ProcessItem> + Unpin + Send;
 
 /// Process a line with mutable OutputStream
 pub async fn parse(line: String, stream: &mut OutputStream) -> Result<Option<Vec<XCOutput>>> {
+    parse_with_state(line, stream, &mut XCParseState::default()).await
+}
+
+/// Process a line with mutable OutputStream, tracking context across lines in [`XCParseState`]
+pub async fn parse_with_state(
+    line: String,
+    stream: &mut OutputStream,
+    state: &mut XCParseState,
+) -> Result<Option<Vec<XCOutput>>> {
     if line.contains("ONLY_ACTIVE_ARCH=YES") {
         return Ok(None);
     }
 
-    let matcher = match XCLOG_MATCHER.capture(&line) {
+    let (timestamp, line) = match split_timestamp(&line) {
+        Some((timestamp, line)) => (timestamp, line.to_string()),
+        None => (SystemTime::now(), line),
+    };
+    let (line, raw) = match strip_ansi(&line) {
+        Cow::Owned(stripped) => (stripped, Some(line)),
+        Cow::Borrowed(_) => (line, None),
+    };
+    let stamp = |mut output: XCOutput| {
+        output.raw = raw.clone();
+        output.timestamp = Some(timestamp);
+        output
+    };
+
+    state.observe_line(&line, timestamp);
+
+    if let Some(outputs) = state
+        .observe_invocation_error_line(&line)
+        .or_else(|| state.observe_failed_command_line(&line))
+    {
+        let outputs = outputs.into_iter().map(stamp).collect::<Vec<_>>();
+        return Ok((!outputs.is_empty()).then_some(outputs));
+    }
+
+    let matcher = XCLOG_MATCHER.capture(&line);
+    if let Some(outputs) = state.observe_script_line(&line, matcher.as_ref()) {
+        outputs.iter().for_each(|output| state.count(output));
+        let outputs = outputs.into_iter().map(stamp).collect::<Vec<_>>();
+        return Ok((!outputs.is_empty()).then_some(outputs));
+    }
+
+    let matcher = match matcher {
         Some(m) => m,
         None => return Ok(None),
     };
 
-    let mut lines = vec![];
+    let mut lines = state
+        .observe_match(&line, &matcher)
+        .into_iter()
+        .map(stamp)
+        .collect::<Vec<_>>();
+    let is_diagnostic = state::is_diagnostic(&matcher);
+    if is_diagnostic {
+        lines.iter().for_each(|output| state.count(output));
+    }
     let line = match matcher.output()? {
-        Some(line) => line,
-        None => return Ok(None),
+        Some(line) => stamp(line),
+        None if lines.is_empty() => return Ok(None),
+        None => return Ok(Some(lines)),
     };
 
+    if is_diagnostic {
+        state.count(&line);
+    }
+    state.observe_step(&matcher, &line);
+
     let (is_compile_warning, is_compile_error) =
         (matcher.is_compile_warning(), matcher.is_compile_error());
 
@@ -39,28 +105,41 @@ pub async fn parse(line: String, stream: &mut OutputStream) -> Result<Option<Vec
             ("[Warning]", XCOutputTask::Warning)
         };
 
-        let whitespace = XCOutput {
-            value: leading.to_string(),
-            kind: kind.clone(),
-        };
+        let mut whitespace = XCOutput::new(kind.clone(), leading);
+        whitespace.timestamp = Some(timestamp);
 
         lines.push(whitespace.clone());
         lines.push(whitespace.clone());
 
         lines.push(line);
 
-        while let Some(line) = stream.next().await.map(|s| s.to_string()) {
+        let mut exit = None;
+        while let Some(item) = stream.next().await {
+            let line = match item {
+                ProcessItem::Output(line) | ProcessItem::Error(line) => line,
+                ProcessItem::Exit(code) => {
+                    exit = Some(state.observe_exit(&code));
+                    break;
+                }
+            };
+            let (timestamp, line) = match split_timestamp(&line) {
+                Some((timestamp, line)) => (timestamp, line),
+                None => (SystemTime::now(), line.as_str()),
+            };
             if line.is_empty() {
                 break;
             }
-            lines.push(XCOutput {
-                value: format!("{leading} {line}"),
-                kind: kind.clone(),
-            });
+            let mut output = XCOutput::new(kind.clone(), format!("{leading} {}", strip_ansi(line)));
+            if ANSI_ESCAPE.is_match(line) {
+                output.raw = Some(line.to_string());
+            }
+            output.timestamp = Some(timestamp);
+            lines.push(output);
         }
 
         lines.push(whitespace.clone());
         lines.push(whitespace);
+        lines.extend(exit);
     } else {
         lines.push(line);
     }
@@ -68,6 +147,21 @@ pub async fn parse(line: String, stream: &mut OutputStream) -> Result<Option<Vec
     Ok(Some(lines))
 }
 
+/// Split leading RFC 3339 timestamp, e.g. `2022-06-25T10:00:00.123Z `, from a replayed log line
+pub(crate) fn split_timestamp(line: &str) -> Option<(SystemTime, &str)> {
+    let (prefix, timestamp) = lazy_regex::regex_captures!(
+        r"^\[?(\d{4}-\d{2}-\d{2}T\d{2}:\d{2}:\d{2}(?:\.\d+)?(?:Z|[+-]\d{2}:\d{2}))\]?\s",
+        line
+    )?;
+    let timestamp = OffsetDateTime::parse(timestamp, &Rfc3339).ok()?;
+    Some((timestamp.into(), &line[prefix.len()..]))
+}
+
+/// Remove ANSI escape sequences, e.g. colours added with `COLOR_DIAGNOSTICS=YES`, from a line
+pub fn strip_ansi(line: &str) -> Cow<'_, str> {
+    ANSI_ESCAPE.replace_all(line, "")
+}
+
 #[tokio::test]
 #[tracing_test::traced_test]
 #[ignore = "Local tests"]
@@ -76,7 +170,7 @@ async fn test_case_2() {
     use process_stream::{ProcessExt, StreamExt};
 
     let root = "/Users/tami5/repos/swift/yabaimaster";
-    let mut logger = XCLogger::new(root, &[
+    let mut logger = XCLogger::new(root, [
         "clean",
         "build",
         "-configuration",
@@ -94,3 +188,27 @@ async fn test_case_2() {
         println!("{}", line)
     }
 }
+
+#[tokio::test]
+#[tracing_test::traced_test]
+async fn ansi_colours() {
+    let line = "\x1b[1m/path/file.swift:64:69: \x1b[0m\x1b[0;1;31merror: \x1b[0m\x1b[1mcannot find 'input' in scope\x1b[0m";
+    let mut stream = async_stream::stream! {
+        yield ProcessItem::Output("\x1b[0;1;32m        ^~~~~\x1b[0m".into());
+        yield ProcessItem::Output("".into());
+    }
+    .boxed();
+
+    let outputs = parse(line.into(), &mut stream).await.unwrap().unwrap();
+    let error = &outputs[2];
+    let cursor = &outputs[3];
+
+    assert!(error.is_error());
+    assert_eq!(
+        error.value,
+        "[Error] /path/file.swift:64:69: cannot find 'input' in scope"
+    );
+    assert_eq!(error.raw.as_deref(), Some(line));
+    assert_eq!(cursor.value, "[Error]         ^~~~~");
+    assert!(cursor.raw.is_some());
+}
@@ -1,5 +1,8 @@
+use serde::{Deserialize, Serialize};
+use std::time::SystemTime;
+
 /// Kinds of outputs
-#[derive(Clone, derive_is_enum_variant::is_enum_variant, Debug)]
+#[derive(Clone, derive_is_enum_variant::is_enum_variant, Debug, Serialize, Deserialize)]
 pub enum XCOutputTask {
     /// Task like Compile, Mkdir ..
     Task,
@@ -12,20 +15,35 @@ pub enum XCOutputTask {
     /// End Result
     Result,
     /// Process exit
-    Exit
+    Exit,
 }
 
 /// Formatted results of a given match
-#[derive(Clone, derive_deref_rs::Deref, Debug)]
+#[derive(Clone, derive_deref_rs::Deref, Debug, Serialize, Deserialize)]
+#[non_exhaustive]
 pub struct XCOutput {
     #[deref]
     /// output value
     pub value: String,
     /// output kind
     pub kind: XCOutputTask,
+    /// Original line including ANSI escape sequences, if any were stripped before matching
+    pub raw: Option<String>,
+    /// When the line producing the output was read
+    pub timestamp: Option<SystemTime>,
 }
 
 impl XCOutput {
+    /// Create new output of a given kind
+    pub fn new<S: Into<String>>(kind: XCOutputTask, value: S) -> Self {
+        Self {
+            value: value.into(),
+            kind,
+            raw: None,
+            timestamp: None,
+        }
+    }
+
     /// Whether the output is a task
     pub fn is_task(&self) -> bool {
         self.kind.is_task()
@@ -0,0 +1,810 @@
+use super::XCMatch;
+use super::{XCOutput, XCOutputTask};
+use crate::result::{XCAction, XCActionOutcome, XCActionResult};
+use crate::result::{XCBuildResult, XCTestCase, XCTestCrash, XCTestStatus};
+use crate::result::{XCDiagnosticSeverity, XCLintDiagnostic, XCScriptDiagnostic, XCScriptOutput};
+use crate::result::{XCFailedCommand, XCInvocationError};
+use crate::result::{XCSlowTypeCheck, XCStep, XCTaskTiming};
+use lazy_regex::{regex, regex_captures as cap};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+/// Maximum number of crash context lines kept for a running test case
+const CRASH_CONTEXT_LIMIT: usize = 20;
+
+/// Parsing context carried across lines of a single build log
+#[derive(Debug, Default)]
+pub struct XCParseState {
+    /// Structured results collected so far
+    pub result: XCBuildResult,
+    /// Crash related lines printed while a test case is running
+    crash_context: Vec<String>,
+    /// Index of the script phase whose output is being read
+    script: Option<usize>,
+    /// Whether the indented command lines of the script phase header are being read
+    script_commands: bool,
+    /// Index of the last step reported for each target
+    open_steps: HashMap<String, usize>,
+    /// Timestamp of the last line read
+    last_timestamp: Option<SystemTime>,
+    /// Artifact reported before the action marker
+    artifact: Option<PathBuf>,
+    /// Errors reported before the action marker
+    action_errors: Vec<String>,
+    /// `xcodebuild: error:` message and the indented lines read after it
+    invocation_error: Option<(String, Vec<String>)>,
+    /// Whether the failed build commands summary is being read
+    failed_commands: bool,
+}
+
+impl XCParseState {
+    /// Record raw line before it gets matched
+    pub(crate) fn observe_line(&mut self, line: &str, timestamp: SystemTime) {
+        self.last_timestamp = Some(timestamp);
+
+        if self.result.tests.running().is_none() {
+            return;
+        }
+
+        let line = line.trim();
+        let is_crash_line = line.contains("Fatal error:")
+            || regex!(r"^Thread \d+ Crashed").is_match(line)
+            || crash_signal(line).is_some();
+
+        if is_crash_line && self.crash_context.len() < CRASH_CONTEXT_LIMIT {
+            self.crash_context.push(line.to_string());
+        }
+    }
+
+    /// Attribute indented line to the last `xcodebuild: error:`, returning outputs to emit if
+    /// it was consumed.
+    pub(crate) fn observe_invocation_error_line(&mut self, line: &str) -> Option<Vec<XCOutput>> {
+        let (_, body) = self.invocation_error.as_mut()?;
+        if !line.trim().is_empty() && !line.starts_with(char::is_whitespace) {
+            self.finish_invocation_error();
+            return None;
+        }
+
+        body.push(line.to_string());
+        if line.trim().is_empty() {
+            return Some(vec![]);
+        }
+        Some(vec![XCOutput::new(
+            XCOutputTask::Error,
+            format!("[Error] {}", line.trim()),
+        )])
+    }
+
+    fn finish_invocation_error(&mut self) {
+        if let Some((message, body)) = self.invocation_error.take() {
+            self.result
+                .invocation_errors
+                .push(XCInvocationError::new(&message, &body));
+        }
+    }
+
+    /// Record line of the failed build commands summary, returning outputs to emit if it was
+    /// consumed.
+    ///
+    /// The summary lasts until `(N failures)` or the first non-indented line.
+    pub(crate) fn observe_failed_command_line(&mut self, line: &str) -> Option<Vec<XCOutput>> {
+        if !self.failed_commands {
+            return None;
+        }
+
+        if let Some((_, count)) = cap!(r"^\((\d+) failures?\)$", line.trim()) {
+            self.failed_commands = false;
+            self.result.failures_count = count.parse().ok();
+            return Some(vec![]);
+        }
+
+        if !line.starts_with(char::is_whitespace) {
+            self.failed_commands = false;
+            return None;
+        }
+
+        let command = XCFailedCommand::new(line)?;
+        let value = format!("[Error] {}", command.command);
+        self.result.failed_commands.push(command);
+        Some(vec![XCOutput::new(XCOutputTask::Error, value)])
+    }
+
+    /// Attribute line to the running script phase, returning outputs to emit if it was consumed.
+    ///
+    /// Script output lasts until the next build step header.
+    pub(crate) fn observe_script_line(
+        &mut self,
+        line: &str,
+        matcher: Option<&XCMatch>,
+    ) -> Option<Vec<XCOutput>> {
+        let idx = self.script?;
+        let is_indented = line.starts_with(char::is_whitespace);
+        let diagnostic = cap!(
+            r"^\s*(?:([^:\s][^:]*:\d+(?::\d+)?):\s)?(?:fatal\s)?(error|warning):\s(.*)$",
+            line
+        );
+        // Script output ends at the next step, summary or failed command
+        let is_end = !is_indented
+            && (regex!(r"^Command\s.*\sfailed with a nonzero exit code$").is_match(line)
+                || matcher
+                    .map(|m| !m.is_swift_lint_diagnostic() && !m.is_swift_format_diagnostic())
+                    .unwrap_or_default()
+                    && diagnostic.is_none());
+
+        if is_end {
+            self.finish_script();
+            return None;
+        }
+
+        if self.script_commands && is_indented {
+            return Some(vec![]);
+        }
+        self.script_commands = false;
+
+        self.result.scripts[idx].output.push(line.to_string());
+
+        let target = self.result.scripts[idx].target.clone();
+        if let Some(output) = matcher.and_then(|m| self.observe_lint(m, Some(target))) {
+            return Some(vec![output]);
+        }
+
+        let script = &mut self.result.scripts[idx];
+
+        let (_, location, severity, message) = diagnostic?;
+        let (severity, leading, kind) = severity_of(severity);
+        let location = (!location.is_empty()).then(|| location.to_string());
+        let value = match &location {
+            Some(location) => format!("{location}: {message}"),
+            None => message.to_string(),
+        };
+
+        script.diagnostics.push(XCScriptDiagnostic {
+            severity,
+            location,
+            message: message.to_string(),
+        });
+
+        Some(vec![XCOutput::new(
+            kind,
+            format!("{leading} [{}] {}: {value}", script.target, script.name),
+        )])
+    }
+
+    /// Record linter rule violation, returning the output to emit.
+    fn observe_lint(&mut self, matcher: &XCMatch, target: Option<String>) -> Option<XCOutput> {
+        let (tool, data) = match matcher {
+            XCMatch::SwiftLintDiagnostic(m) => {
+                let d = m.as_data();
+                ("SwiftLint", (d.location, d.severity, d.message, d.rule))
+            }
+            XCMatch::SwiftFormatDiagnostic(m) => {
+                let d = m.as_data();
+                ("SwiftFormat", (d.location, d.severity, d.message, d.rule))
+            }
+            _ => return None,
+        };
+        let (location, severity, message, rule) = data;
+        let (severity, leading, kind) = severity_of(&severity);
+        let output = XCOutput::new(
+            kind,
+            format!("{leading} [{tool}] {location}: {message} ({rule})"),
+        );
+
+        self.result.lints.push(XCLintDiagnostic {
+            tool: tool.to_string(),
+            rule,
+            severity,
+            location,
+            message,
+            target,
+        });
+
+        Some(output)
+    }
+
+    /// Record build step, closing the previous step of the same target.
+    pub(crate) fn observe_step(&mut self, matcher: &XCMatch, output: &XCOutput) {
+        if matcher.is_phase_success() || matcher.is_phase_failed() {
+            if let Some(ended) = output.timestamp {
+                self.close_steps(ended);
+            }
+            return;
+        }
+
+        let target = match matcher.get("target") {
+            Some(target) if !target.is_empty() => target,
+            _ => return,
+        };
+        let started = match output.timestamp {
+            Some(timestamp) if matcher.is_task() && !matcher.name().ends_with("Target") => {
+                timestamp
+            }
+            _ => return,
+        };
+
+        if let Some(idx) = self.open_steps.remove(target) {
+            let step = &mut self.result.steps[idx];
+            step.duration = started.duration_since(step.started).ok();
+        }
+
+        self.open_steps
+            .insert(target.to_string(), self.result.steps.len());
+        self.result.steps.push(XCStep {
+            kind: matcher.name().to_string(),
+            target: target.to_string(),
+            description: output.value.clone(),
+            started,
+            duration: None,
+        });
+    }
+
+    /// Count error or warning reported by a diagnostic output, see [`is_diagnostic`]
+    pub(crate) fn count(&mut self, output: &XCOutput) {
+        match output.kind {
+            XCOutputTask::Error => self.result.errors_count += 1,
+            XCOutputTask::Warning => self.result.warnings_count += 1,
+            _ => (),
+        }
+    }
+
+    /// Record the exit code of xcodebuild, returning its exit output
+    pub(crate) fn observe_exit(&mut self, exit: &str) -> XCOutput {
+        let value = exit.trim();
+        self.result.exit_code = value.parse().ok();
+        XCOutput::new(XCOutputTask::Exit, value)
+    }
+
+    /// Mark the end of the log, closing steps and script output that are still open.
+    pub fn finish(&mut self) {
+        self.finish_script();
+        self.finish_invocation_error();
+
+        if let Some(ended) = self.last_timestamp {
+            self.close_steps(ended);
+        }
+    }
+
+    /// Close every open step at given time
+    fn close_steps(&mut self, ended: SystemTime) {
+        for (_, idx) in self.open_steps.drain() {
+            let step = &mut self.result.steps[idx];
+            step.duration = ended.duration_since(step.started).ok();
+        }
+    }
+
+    /// Mark the end of script output
+    pub(crate) fn finish_script(&mut self) {
+        if let Some(idx) = self.script.take() {
+            let output = &mut self.result.scripts[idx].output;
+            while output
+                .last()
+                .map(|l| l.trim().is_empty())
+                .unwrap_or_default()
+            {
+                output.pop();
+            }
+        }
+    }
+
+    /// Update state from a matched line, returning additional outputs to emit.
+    pub(crate) fn observe_match(&mut self, line: &str, matcher: &XCMatch) -> Vec<XCOutput> {
+        if let Some(output) = self.observe_lint(matcher, None) {
+            return vec![output];
+        }
+
+        let tests = &mut self.result.tests;
+        match matcher {
+            XCMatch::SlowTypeCheckWarning(_) | XCMatch::SlowTypeCheckTiming(_) => {
+                let get = |name| matcher.get(name).unwrap_or_default();
+                self.result.slow_type_checks.push(XCSlowTypeCheck {
+                    path: get("filepath").into(),
+                    line: get("line").parse().unwrap_or_default(),
+                    column: get("column").parse().unwrap_or_default(),
+                    symbol: get("symbol").to_string(),
+                    ms: get("ms").parse().unwrap_or_default(),
+                });
+            }
+            XCMatch::BuildCommandsFailed(_) => {
+                self.failed_commands = true;
+            }
+            XCMatch::XcodebuildError(m) => {
+                self.invocation_error = Some((m.as_data().message, vec![]));
+            }
+            XCMatch::ExportedArtifact(m) => {
+                self.artifact = Some(m.as_data().path.into());
+            }
+            XCMatch::ExportArchiveError(m) => {
+                self.action_errors.push(m.as_data().message);
+            }
+            XCMatch::PhaseSuccess(_) | XCMatch::PhaseFailed(_) => {
+                let outcome = if matcher.is_phase_success() {
+                    XCActionOutcome::Succeeded
+                } else {
+                    XCActionOutcome::Failed
+                };
+                self.result.actions.push(XCActionResult {
+                    action: XCAction::from_marker(matcher.get("name").unwrap_or_default()),
+                    outcome,
+                    artifact: self.artifact.take(),
+                    errors: std::mem::take(&mut self.action_errors),
+                });
+            }
+            XCMatch::BuildTimingSummaryItem(m) => {
+                let data = m.as_data();
+                self.result.timing_summary.push(XCTaskTiming {
+                    task: data.task,
+                    count: data.count.parse().unwrap_or_default(),
+                    seconds: data.seconds.parse().unwrap_or_default(),
+                });
+            }
+            XCMatch::PhaseScriptExecution(m) => {
+                let data = m.as_data();
+                self.result.scripts.push(XCScriptOutput {
+                    name: data.name.replace("\\ ", " "),
+                    target: data.target,
+                    project: data.project,
+                    ..Default::default()
+                });
+                self.script = Some(self.result.scripts.len() - 1);
+                self.script_commands = true;
+            }
+            XCMatch::TestCaseStarted(m) => {
+                let data = m.as_data();
+                self.crash_context.clear();
+                tests.start(&data.suite, &data.case);
+            }
+            XCMatch::TestCasePassed(_)
+            | XCMatch::ParallelTestCasePassed(_)
+            | XCMatch::ParallelTestCaseAppKitPassed(_)
+            | XCMatch::TestCaseFailed(_)
+            | XCMatch::ParallelTestCaseFailed(_) => {
+                let get = |name| matcher.get(name).unwrap_or_default();
+                let case = tests.get_mut(get("suite"), get("case"));
+                case.status = if matcher.is_error() {
+                    XCTestStatus::Failed
+                } else {
+                    XCTestStatus::Passed
+                };
+                case.duration = get("time").parse().ok();
+            }
+            XCMatch::KiwiFailingTest(m) => {
+                let data = m.as_data();
+                let case = tests.get_mut(&data.suite, &data.case);
+                case.failures
+                    .push(format!("{}: {}", data.filepath, data.reason));
+            }
+            XCMatch::RestartingTests(m) => {
+                let data = m.as_data();
+                let case = if data.suite.is_empty() {
+                    tests.running_mut()
+                } else {
+                    Some(tests.get_mut(&data.suite, &data.case))
+                };
+
+                let case = match case {
+                    Some(case) => case,
+                    None => return vec![],
+                };
+
+                let messages = std::mem::take(&mut self.crash_context);
+                let signal = crash_signal(line)
+                    .or_else(|| messages.iter().find_map(|line| crash_signal(line)));
+
+                case.status = XCTestStatus::Crashed;
+                case.crash = Some(XCTestCrash { signal, messages });
+
+                return vec![crash_output(case)];
+            }
+            _ => {}
+        }
+        vec![]
+    }
+}
+
+/// Whether matcher reports a compiler, linker, lint or `error:` diagnostic, as opposed to action
+/// markers, summaries and context lines that happen to be errors.
+pub(crate) fn is_diagnostic(matcher: &XCMatch) -> bool {
+    matches!(
+        matcher,
+        XCMatch::CompileWarning(_)
+            | XCMatch::LdWarning(_)
+            | XCMatch::GenericWarning(_)
+            | XCMatch::CodeSignWarning(_)
+            | XCMatch::ClangError(_)
+            | XCMatch::CheckDependenciesError(_)
+            | XCMatch::ProvisioningProfileRequiredError(_)
+            | XCMatch::NoCertificateError(_)
+            | XCMatch::CompileError(_)
+            | XCMatch::FatalError(_)
+            | XCMatch::FileMissingError(_)
+            | XCMatch::LdError(_)
+            | XCMatch::LinkerDuplicateSymbolsError(_)
+            | XCMatch::LinkerUndefinedSymbolsError(_)
+            | XCMatch::PodsError(_)
+            | XCMatch::ModuleIncludesError(_)
+            | XCMatch::ExportArchiveError(_)
+            | XCMatch::XcodebuildError(_)
+            | XCMatch::SwiftLintDiagnostic(_)
+            | XCMatch::SwiftFormatDiagnostic(_)
+    )
+}
+
+/// Map `error`/`warning` to diagnostic severity, output leading and output kind
+fn severity_of(severity: &str) -> (XCDiagnosticSeverity, &'static str, XCOutputTask) {
+    match severity {
+        "error" => (XCDiagnosticSeverity::Error, "[Error]", XCOutputTask::Error),
+        _ => (
+            XCDiagnosticSeverity::Warning,
+            "[Warning]",
+            XCOutputTask::Warning,
+        ),
+    }
+}
+
+/// Extract the signal name from a crash report line
+fn crash_signal(line: &str) -> Option<String> {
+    cap!(r"\b(SIG[A-Z]{2,6})\b", line)
+        .or_else(|| cap!(r"(?i)(?:with|due\sto)\ssignal\s(?:code\s)?(\w+)", line))
+        .map(|(_, signal)| signal.to_string())
+}
+
+fn crash_output(case: &XCTestCase) -> XCOutput {
+    let crash = case.crash.as_ref();
+    let signal = crash
+        .and_then(|c| c.signal.as_ref())
+        .map(|s| format!(" ({s})"))
+        .unwrap_or_default();
+    let message = crash
+        .and_then(|c| c.messages.iter().find(|l| l.contains("Fatal error:")))
+        .map(|s| format!(": {s}"))
+        .unwrap_or_default();
+
+    XCOutput::new(
+        XCOutputTask::Error,
+        format!(
+            "[Error] [Crashed] {}.{}{signal}{message}",
+            case.suite, case.name
+        ),
+    )
+}
+
+/// Replay given log, returning its outputs and build result
+#[cfg(test)]
+async fn replay(content: &str) -> (Vec<XCOutput>, XCBuildResult) {
+    use process_stream::StreamExt;
+
+    let reader = std::io::Cursor::new(content.to_string().into_bytes());
+    let mut logger = crate::XCLogger::from_reader(reader);
+    let outputs = logger
+        .spawn_and_stream_outputs()
+        .unwrap()
+        .collect::<Vec<_>>()
+        .await;
+    let result = logger.result.lock().await.clone();
+    (outputs, result)
+}
+
+#[tokio::test]
+#[tracing_test::traced_test]
+async fn test_host_crash() {
+    let (outputs, result) = replay(r#"Test Suite 'FooTests' started at 2022-06-25 10:00:00.000
+Test Case '-[AppTests.FooTests testPass]' started.
+Test Case '-[AppTests.FooTests testPass]' passed (0.001 seconds).
+Test Case '-[AppTests.FooTests testCrash]' started.
+AppTests/FooTests.swift:12: Fatal error: Unexpectedly found nil while unwrapping an Optional value
+Thread 0 Crashed:: Dispatch queue: com.apple.main-thread
+App (12345) crashed with signal ill
+Restarting after unexpected exit, crash, or test timeout in FooTests.testCrash(); summary will include totals from previous launches."#).await;
+    let crashed = result.tests.get("FooTests", "testCrash").unwrap();
+    let crash = crashed.crash.as_ref().unwrap();
+
+    assert!(crashed.status.is_crashed());
+    assert!(result
+        .tests
+        .get("FooTests", "testPass")
+        .unwrap()
+        .status
+        .is_passed());
+    assert_eq!(crash.signal.as_deref(), Some("ill"));
+    assert_eq!(crash.messages.len(), 3);
+    assert!(outputs
+        .iter()
+        .any(|o| o.is_error() && o.value.contains("AppTests.FooTests.testCrash (ill)")));
+}
+
+#[tokio::test]
+#[tracing_test::traced_test]
+async fn parallel_test_cases() {
+    let (outputs, result) = replay(r#"Test case 'FooTests.testPass()' passed on 'Clone 1 of iPhone 14 - App (12345)' (0.001 seconds)
+Test case 'FooTests.testBar()' failed on 'Clone 2 of iPhone 14 - App (12346)' (0.010 seconds)
+Test case '-[BarTests testPass]' passed on 'Clone 1 of iPhone 14 - App (12345)' (0.002 seconds)."#).await;
+
+    assert_eq!(result.tests.len(), 3);
+    assert!(result
+        .tests
+        .get("FooTests", "testPass")
+        .unwrap()
+        .status
+        .is_passed());
+    assert!(result
+        .tests
+        .get("BarTests", "testPass")
+        .unwrap()
+        .status
+        .is_passed());
+    let failed = result.tests.get("FooTests", "testBar").unwrap();
+    assert!(failed.status.is_failed());
+    assert_eq!(failed.duration, Some(0.01));
+    assert!(outputs.is_empty());
+}
+
+#[tokio::test]
+#[tracing_test::traced_test]
+async fn script_phase_output() {
+    let (outputs, result) = replay(r#"PhaseScriptExecution Check\ Pods\ Manifest.lock /path/to/Script-1.sh (in target 'App' from project 'App')
+    cd /path/to/App
+    /bin/sh -c /path/to/Script-1.sh
+Checking manifest
+error: The sandbox is not in sync with the Podfile.lock. Run 'pod install'.
+warning: Podfile.lock is outdated
+
+CompileSwift normal arm64 /path/to/App/AppDelegate.swift (in target 'App' from project 'App')"#).await;
+    let script = &result.scripts[0];
+
+    assert_eq!(script.name, "Check Pods Manifest.lock");
+    assert_eq!(script.target, "App");
+    assert_eq!(script.output.len(), 3);
+    assert_eq!(script.diagnostics.len(), 2);
+    assert!(script.diagnostics[0].severity.is_error());
+    assert_eq!(
+        outputs.iter().map(|o| o.value.as_str()).collect::<Vec<_>>(),
+        vec![
+            "[App] Executing Check\\ Pods\\ Manifest.lock",
+            "[Error] [App] Check Pods Manifest.lock: The sandbox is not in sync with the Podfile.lock. Run 'pod install'.",
+            "[Warning] [App] Check Pods Manifest.lock: Podfile.lock is outdated",
+            "[App] Compiling AppDelegate.swift"
+        ]
+    );
+}
+
+#[tokio::test]
+#[tracing_test::traced_test]
+async fn failing_script_phase() {
+    let (outputs, result) = replay(r#"PhaseScriptExecution Check\ Pods\ Manifest.lock /path/to/Script-1.sh (in target 'App' from project 'App')
+    /bin/sh -c /path/to/Script-1.sh
+error: The sandbox is not in sync with the Podfile.lock. Run 'pod install'.
+Command PhaseScriptExecution failed with a nonzero exit code
+
+** BUILD FAILED **
+
+
+The following build commands failed:
+	PhaseScriptExecution Check\ Pods\ Manifest.lock /path/to/Script-1.sh (in target 'App' from project 'App')
+(1 failure)"#).await;
+    let script = &result.scripts[0];
+
+    assert_eq!(script.output.len(), 1);
+    assert_eq!(script.diagnostics.len(), 1);
+    assert!(result.action().unwrap().outcome.is_failed());
+    assert_eq!(result.failed_commands.len(), 1);
+    assert!(outputs.iter().any(|o| o.value == "[Error] [BUILD] Failed"));
+}
+
+#[tokio::test]
+#[tracing_test::traced_test]
+async fn lint_diagnostics() {
+    let (outputs, result) = replay(r#"PhaseScriptExecution SwiftLint /path/to/Script-2.sh (in target 'App' from project 'App')
+    /bin/sh -c /path/to/Script-2.sh
+Linting Swift files in current working directory
+/path/to/App/AppDelegate.swift:10:5: warning: Line Length Violation: Line should be 120 characters or less: currently 130 characters (line_length)
+/path/to/App/AppDelegate.swift:12:1: warning: (indent) Indent code in accordance with the scope level.
+/path/to/App/AppDelegate.swift:14:1: warning: 'flatMap' is deprecated
+/path/to/App/AppDelegate.swift:16:9: error: (redundantSelf) Remove explicit self where applicable.
+Done linting! Found 1 violation, 0 serious in 1 file."#).await;
+
+    assert_eq!(result.lints.len(), 3);
+    assert_eq!(result.lints[0].tool, "SwiftLint");
+    assert_eq!(result.lints[0].rule, "line_length");
+    assert_eq!(result.lints[0].target.as_deref(), Some("App"));
+    assert_eq!(result.lints[1].tool, "SwiftFormat");
+    assert_eq!(result.lints[1].rule, "indent");
+    assert!(result.lints[2].severity.is_error());
+    assert!(outputs[4].is_error());
+    assert_eq!(result.scripts[0].diagnostics.len(), 1);
+    assert_eq!(
+        outputs[1].value,
+        "[Warning] [SwiftLint] /path/to/App/AppDelegate.swift:10:5: Line Length Violation: Line should be 120 characters or less: currently 130 characters (line_length)"
+    );
+}
+
+#[tokio::test]
+#[tracing_test::traced_test]
+async fn step_durations() {
+    use std::time::Duration;
+
+    let (outputs, result) = replay(r#"2022-06-25T10:00:00Z PhaseScriptExecution SwiftLint /path/to/Script-1.sh (in target 'App' from project 'App')
+2022-06-25T10:00:00Z     /bin/sh -c /path/to/Script-1.sh
+2022-06-25T10:00:01Z Linting Swift files in current working directory
+2022-06-25T10:00:01Z CompileSwift normal arm64 /path/to/App/AppDelegate.swift (in target 'App' from project 'App')
+2022-06-25T10:00:02Z Ld /path/to/App.app/App.o normal (in target 'App' from project 'App')
+2022-06-25T10:00:05Z CodeSign /path/to/App.app (in target 'App' from project 'App')
+2022-06-25T10:00:05Z CompileSwift normal arm64 /path/to/Lib/Lib.swift (in target 'Lib' from project 'App')
+2022-06-25T10:00:05.500Z Ld /path/to/Lib.framework/Lib normal (in target 'Lib' from project 'App')
+2022-06-25T10:00:06Z ** BUILD SUCCEEDED **
+2022-06-25T10:00:30Z ** TEST SUCCEEDED **"#).await;
+    let durations = result
+        .steps
+        .iter()
+        .map(|s| (s.kind.as_str(), s.target.as_str(), s.duration))
+        .collect::<Vec<_>>();
+
+    assert!(outputs.iter().all(|o| o.timestamp.is_some()));
+    assert_eq!(
+        durations,
+        vec![
+            ("PhaseScriptExecution", "App", Some(Duration::from_secs(1))),
+            ("Compile", "App", Some(Duration::from_secs(1))),
+            ("Linking", "App", Some(Duration::from_secs(3))),
+            ("CodeSign", "App", Some(Duration::from_secs(1))),
+            ("Compile", "Lib", Some(Duration::from_millis(500))),
+            ("Linking", "Lib", Some(Duration::from_millis(500))),
+        ]
+    );
+    assert_eq!(result.slowest_steps(1)[0].kind, "Linking");
+    assert_eq!(result.scripts[0].output.len(), 1);
+}
+
+#[tokio::test]
+#[tracing_test::traced_test]
+async fn build_timing_summary() {
+    let (_, result) = replay("Build Timing Summary\n\nCompileSwiftSources (4 tasks) | 12.345 seconds\n\nLd (1 task) | 0.123 seconds\n\nCopy Swift standard libraries (2 tasks) | 1.5 seconds\n\n** BUILD SUCCEEDED ** [13.120 sec]").await;
+    let summary = &result.timing_summary;
+
+    assert_eq!(summary.len(), 3);
+    assert_eq!(summary[0].task, "CompileSwiftSources");
+    assert_eq!(summary[0].count, 4);
+    assert_eq!(summary[0].seconds, 12.345);
+    assert_eq!(summary[1].task, "Ld");
+    assert_eq!(summary[1].count, 1);
+    assert_eq!(summary[2].task, "Copy Swift standard libraries");
+}
+
+#[tokio::test]
+#[tracing_test::traced_test]
+async fn slow_type_checks() {
+    let (outputs, result) = replay("/path/to/A.swift:12:10: warning: instance method 'foo()' took 350ms to type-check (limit: 200ms)\n\
+            /path/to/A.swift:30:5: warning: 'flatMap' is deprecated\n\
+            \n\
+            410.20ms\t/path/to/A.swift:12:10\tinstance method foo()\n\
+            0.10ms\t/path/to/B.swift:3:8\tgetter body\n\
+            /path/to/B.swift:20:5: warning: expression took 220ms to type-check (limit: 200ms)").await;
+    let report = result
+        .slowest_type_checks(2)
+        .into_iter()
+        .map(|c| (c.path.to_str().unwrap(), c.line, c.ms))
+        .collect::<Vec<_>>();
+
+    assert_eq!(result.slow_type_checks.len(), 4);
+    assert_eq!(
+        report,
+        vec![
+            ("/path/to/A.swift", 12, 410.2),
+            ("/path/to/B.swift", 20, 220.0)
+        ]
+    );
+    assert_eq!(
+        outputs[0].value,
+        "[Warning] [TypeCheck] /path/to/A.swift:12:10: instance method 'foo()' took 350ms"
+    );
+    assert_eq!(outputs.iter().filter(|o| o.kind.is_warning()).count(), 7);
+    assert_eq!(result.warnings_count, 1);
+}
+
+#[tokio::test]
+#[tracing_test::traced_test]
+async fn archive_and_export() {
+    let (outputs, result) = replay(
+        "** ARCHIVE SUCCEEDED **\n\
+            error: exportArchive: No signing certificate \"iOS Distribution\" found\n\
+            ** EXPORT FAILED **\n\
+            Exported Example to: /path/to/Export\n\
+            ** EXPORT SUCCEEDED **",
+    )
+    .await;
+    let archive = &result.actions[0];
+    let failed_export = &result.actions[1];
+    let export = result.action().unwrap();
+
+    assert!(archive.action.is_archive() && archive.outcome.is_succeeded());
+    assert!(failed_export.action.is_export() && failed_export.outcome.is_failed());
+    assert_eq!(failed_export.errors.len(), 1);
+    assert!(export.outcome.is_succeeded());
+    assert_eq!(
+        export.artifact.as_deref(),
+        Some(std::path::Path::new("/path/to/Export"))
+    );
+    assert_eq!(outputs[0].value, "[ARCHIVE] Succeeded");
+    assert!(outputs[2].is_error());
+}
+
+#[tokio::test]
+#[tracing_test::traced_test]
+async fn invocation_errors() {
+    use crate::XCInvocationError;
+    let (outputs, result) = replay("xcodebuild: error: Unable to find a destination matching the provided destination specifier:\n\
+            \t\t{ platform:iOS Simulator, OS:latest, name:iPhone 99 }\n\
+            \n\
+            \tAvailable destinations for the \"Example\" scheme:\n\
+            \t\t{ platform:macOS, arch:arm64, id:00006000-001A, name:My Mac }\n\
+            \t\t{ platform:iOS Simulator, id:5C1E3B0A, OS:16.0, name:iPhone 14 }\n\
+            \n\
+            \tIneligible destinations for the \"Example\" scheme:\n\
+            \t\t{ platform:iOS, id:dvtdevice-DVTiPhonePlaceholder-iphoneos:placeholder, name:Any iOS Device }\n\
+            \n\
+            xcodebuild: error: The project named \"Example\" does not contain a scheme named \"Missing\". The \"-list\" option can be used to find the names of the schemes in the project.\n\
+            xcodebuild: error: Could not resolve package dependencies:\n  \
+            Failed to clone repository https://github.com/example/missing.git").await;
+
+    assert_eq!(result.invocation_errors.len(), 3);
+    match &result.invocation_errors[0] {
+        XCInvocationError::DestinationNotFound {
+            specifier,
+            available,
+            ineligible,
+        } => {
+            assert_eq!(specifier.name.as_deref(), Some("iPhone 99"));
+            assert_eq!(available[1].os.as_deref(), Some("16.0"));
+            assert_eq!(available.len(), 2);
+            assert_eq!(ineligible.len(), 1);
+        }
+        error => panic!("Unexpected {error:?}"),
+    }
+    assert_eq!(
+        result.invocation_errors[1],
+        XCInvocationError::SchemeNotFound {
+            container: "Example".into(),
+            scheme: "Missing".into()
+        }
+    );
+    assert_eq!(
+        result.invocation_errors[2],
+        XCInvocationError::PackageResolutionFailed {
+            reasons: vec![
+                "Failed to clone repository https://github.com/example/missing.git".into()
+            ]
+        }
+    );
+    assert!(outputs[0].is_error());
+    assert_eq!(
+        outputs[1].value,
+        "[Error] { platform:iOS Simulator, OS:latest, name:iPhone 99 }"
+    );
+}
+
+#[tokio::test]
+#[tracing_test::traced_test]
+async fn failed_commands_summary() {
+    let (outputs, result) = replay("The following build commands failed:\n\
+            \tCompileSwift normal arm64 /path/to/App/Sources/Some\\ File.swift (in target 'App' from project 'App')\n\
+            \tLd /path/to/Build/App.app/App normal (in target 'App' from project 'App')\n\
+            \tCompileSwiftSources normal arm64 com.apple.xcode.tools.swift.compiler (in target 'Core' from project 'Core')\n\
+            (3 failures)\n\
+            ** BUILD FAILED **").await;
+    let commands = &result.failed_commands;
+
+    assert_eq!(commands.len(), 3);
+    assert_eq!(result.failures_count, Some(3));
+    assert_eq!(commands[0].step, "CompileSwift");
+    assert_eq!(
+        commands[0].file.as_deref(),
+        Some(std::path::Path::new("/path/to/App/Sources/Some File.swift"))
+    );
+    assert_eq!(commands[0].target.as_deref(), Some("App"));
+    assert_eq!(commands[0].project.as_deref(), Some("App"));
+    assert_eq!(commands[1].step, "Ld");
+    assert_eq!(commands[2].file, None);
+    assert_eq!(commands[2].target.as_deref(), Some("Core"));
+    assert!(result.action().unwrap().outcome.is_failed());
+    assert!(outputs[..4].iter().all(|o| o.is_error()));
+}
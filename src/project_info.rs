@@ -0,0 +1,227 @@
+use crate::XCRunner;
+use anyhow::{bail, Context, Result};
+use lazy_regex::regex_captures as cap;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Targets, build configurations and schemes reported by `xcodebuild -list`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct XCProjectInfo {
+    /// Project or workspace name
+    pub name: String,
+    /// Whether the info describes a workspace
+    pub is_workspace: bool,
+    /// Project targets, empty for workspaces
+    pub targets: Vec<String>,
+    /// Project build configurations, empty for workspaces
+    pub configurations: Vec<String>,
+    /// Configuration used when neither configuration nor scheme is passed
+    pub default_configuration: Option<String>,
+    /// Project or workspace schemes
+    pub schemes: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct XCListJson {
+    project: Option<XCListJsonInfo>,
+    workspace: Option<XCListJsonInfo>,
+}
+
+#[derive(Deserialize)]
+struct XCListJsonInfo {
+    name: String,
+    #[serde(default)]
+    targets: Vec<String>,
+    #[serde(default)]
+    configurations: Vec<String>,
+    #[serde(default)]
+    schemes: Vec<String>,
+}
+
+impl XCProjectInfo {
+    /// Get project info from given root and build arguments, e.g. `-workspace App.xcworkspace`
+    pub async fn new<P, I, S>(root: P, args: I) -> Result<Self>
+    where
+        P: AsRef<Path> + Send,
+        I: IntoIterator<Item = S> + Send,
+        S: AsRef<std::ffi::OsStr> + Send,
+    {
+        Self::new_with_runner(&XCRunner::default(), root, args).await
+    }
+
+    /// Get project info from given root and build arguments using xcodebuild configured by
+    /// [`XCRunner`]
+    pub async fn new_with_runner<P, I, S>(runner: &XCRunner, root: P, args: I) -> Result<Self>
+    where
+        P: AsRef<Path> + Send,
+        I: IntoIterator<Item = S> + Send,
+        S: AsRef<std::ffi::OsStr> + Send,
+    {
+        let args = args.into_iter().map(|a| a.as_ref().to_os_string());
+        let args = args.chain(["-list".into(), "-json".into()]);
+
+        Self::from_json(&runner.output(root, args).await?)
+    }
+
+    /// Get project info from given root and build arguments
+    pub fn new_sync<P, I, S>(root: P, args: I) -> Result<Self>
+    where
+        P: AsRef<Path> + Send,
+        I: IntoIterator<Item = S> + Send,
+        S: AsRef<std::ffi::OsStr> + Send,
+    {
+        Self::new_sync_with_runner(&XCRunner::default(), root, args)
+    }
+
+    /// Get project info from given root and build arguments using xcodebuild configured by
+    /// [`XCRunner`]
+    pub fn new_sync_with_runner<P, I, S>(runner: &XCRunner, root: P, args: I) -> Result<Self>
+    where
+        P: AsRef<Path> + Send,
+        I: IntoIterator<Item = S> + Send,
+        S: AsRef<std::ffi::OsStr> + Send,
+    {
+        let mut process = runner.command(root);
+
+        process.args(args);
+        process.args(["-list", "-json"]);
+
+        let output = process.output()?;
+        if !output.status.success() {
+            bail!(String::from_utf8(output.stderr)?)
+        }
+        Self::from_json(&String::from_utf8(output.stdout)?)
+    }
+
+    /// Parse `xcodebuild -list -json` output
+    pub fn from_json(content: &str) -> Result<Self> {
+        // xcodebuild may print warnings before the JSON object
+        let content = &content[content.find('{').context("Missing JSON object")?..];
+        let list: XCListJson = serde_json::from_str(content)?;
+        let (info, is_workspace) = match (list.project, list.workspace) {
+            (Some(info), _) => (info, false),
+            (None, Some(info)) => (info, true),
+            (None, None) => bail!("Missing project or workspace in {content}"),
+        };
+
+        Ok(Self {
+            name: info.name,
+            is_workspace,
+            targets: info.targets,
+            configurations: info.configurations,
+            default_configuration: None,
+            schemes: info.schemes,
+        })
+    }
+
+    /// Parse `xcodebuild -list` output
+    pub fn from_lines<'a, I: IntoIterator<Item = &'a str>>(lines: I) -> Result<Self> {
+        let mut info = None::<Self>;
+        let mut section = None;
+
+        for line in lines {
+            let trimmed = line.trim();
+            if let Some((_, kind, name)) = cap!(
+                r#"^Information about (project|workspace) "(.*)":$"#,
+                trimmed
+            ) {
+                info = Some(Self {
+                    name: name.to_string(),
+                    is_workspace: kind == "workspace",
+                    ..Default::default()
+                });
+                continue;
+            }
+
+            let info = match info.as_mut() {
+                Some(info) => info,
+                None => continue,
+            };
+
+            if trimmed.is_empty() {
+                section = None;
+            } else if let Some((_, name)) = cap!(
+                r#"^If no build configuration is specified and -scheme is not passed then "(.*)" is used"#,
+                trimmed
+            ) {
+                info.default_configuration = Some(name.to_string());
+            } else if trimmed.ends_with(':') {
+                section = Some(trimmed);
+            } else {
+                let list = match section {
+                    Some("Targets:") => &mut info.targets,
+                    Some("Build Configurations:") => &mut info.configurations,
+                    Some("Schemes:") => &mut info.schemes,
+                    _ => continue,
+                };
+                list.push(trimmed.to_string());
+            }
+        }
+
+        info.context("Missing `Information about` header")
+    }
+}
+
+#[test]
+fn test_project_info_from_lines() {
+    let content = r#"Command line invocation:
+    /Applications/Xcode.app/Contents/Developer/usr/bin/xcodebuild -list
+
+Information about project "App":
+    Targets:
+        App
+        AppTests
+
+    Build Configurations:
+        Debug
+        Release
+
+    If no build configuration is specified and -scheme is not passed then "Release" is used.
+
+    Schemes:
+        App
+        App Staging
+"#;
+    let info = XCProjectInfo::from_lines(content.lines()).unwrap();
+
+    assert_eq!(info.name, "App");
+    assert!(!info.is_workspace);
+    assert_eq!(info.targets, vec!["App", "AppTests"]);
+    assert_eq!(info.configurations, vec!["Debug", "Release"]);
+    assert_eq!(info.default_configuration.as_deref(), Some("Release"));
+    assert_eq!(info.schemes, vec!["App", "App Staging"]);
+
+    let content =
+        "Information about workspace \"App\":\n    Schemes:\n        App\n        Pods-App\n";
+    let info = XCProjectInfo::from_lines(content.lines()).unwrap();
+
+    assert!(info.is_workspace);
+    assert_eq!(info.schemes, vec!["App", "Pods-App"]);
+    assert!(info.targets.is_empty());
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_project_info_from_json() {
+    let script = crate::runner::StandIn::new(
+        r#"#!/bin/sh
+echo 'warning: stale file outside of the project'
+echo '{ "workspace" : { "name" : "App", "schemes" : [ "App", "Pods-App" ] } }'
+"#,
+    );
+    let args = ["-workspace", "App.xcworkspace"];
+    let info = XCProjectInfo::new_with_runner(&script.runner(), std::env::temp_dir(), args)
+        .await
+        .unwrap();
+
+    assert_eq!(info.name, "App");
+    assert!(info.is_workspace);
+    assert_eq!(info.schemes, vec!["App", "Pods-App"]);
+
+    let content = r#"{"project":{"configurations":["Debug","Release"],"name":"App","schemes":["App"],"targets":["App","AppTests"]}}"#;
+    let info = XCProjectInfo::from_json(content).unwrap();
+
+    assert!(!info.is_workspace);
+    assert_eq!(info.targets, vec!["App", "AppTests"]);
+    assert_eq!(info.configurations, vec!["Debug", "Release"]);
+}
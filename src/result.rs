@@ -0,0 +1,108 @@
+//! Structured results collected while processing xcodebuild logs
+
+mod action;
+mod failure;
+mod invocation;
+mod lint;
+mod outcome;
+mod script;
+mod testing;
+mod timing;
+
+use crate::{XCCancelReason, XCCompileCommand};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+pub use action::{XCAction, XCActionOutcome, XCActionResult};
+pub use failure::XCFailedCommand;
+pub use invocation::XCInvocationError;
+pub use lint::XCLintDiagnostic;
+pub use outcome::XCBuildOutcome;
+pub use script::{XCDiagnosticSeverity, XCScriptDiagnostic, XCScriptOutput};
+pub use testing::{XCTestCase, XCTestCrash, XCTestResults, XCTestStatus};
+pub use timing::{XCSlowTypeCheck, XCStep, XCTaskTiming};
+
+/// Structured data collected from a single xcodebuild run
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct XCBuildResult {
+    /// Test cases reported by the run
+    pub tests: XCTestResults,
+    /// Run script build phases and their output
+    pub scripts: Vec<XCScriptOutput>,
+    /// Linter rule violations, kept apart from compiler warnings
+    pub lints: Vec<XCLintDiagnostic>,
+    /// Build steps in the order they were reported
+    pub steps: Vec<XCStep>,
+    /// Build Timing Summary, present when built with `-showBuildTimingSummary`
+    pub timing_summary: Vec<XCTaskTiming>,
+    /// Slow type-checking reports, kept apart from compiler warnings
+    pub slow_type_checks: Vec<XCSlowTypeCheck>,
+    /// Actions reported by `** <ACTION> SUCCEEDED/FAILED **` markers
+    pub actions: Vec<XCActionResult>,
+    /// Errors caused by the xcodebuild invocation itself
+    pub invocation_errors: Vec<XCInvocationError>,
+    /// Commands listed under `The following build commands failed:`
+    pub failed_commands: Vec<XCFailedCommand>,
+    /// Failure count printed after the failed commands
+    pub failures_count: Option<usize>,
+    /// Exit code of the xcodebuild process, if it exited normally
+    pub exit_code: Option<i32>,
+    /// Number of compiler, linker, lint and `error:` diagnostics, excluding their context lines,
+    /// action markers and summaries
+    pub errors_count: usize,
+    /// Number of compiler, linker, lint and `warning:` diagnostics, excluding their context lines
+    pub warnings_count: usize,
+    /// Compile commands collected from the log
+    pub compile_commands: Vec<XCCompileCommand>,
+    /// File the raw output was written to, if any
+    pub log_path: Option<PathBuf>,
+    /// Reason the build was cancelled, if it was
+    pub cancelled: Option<XCCancelReason>,
+    /// Signal that terminated the xcodebuild process, if any
+    pub signal: Option<i32>,
+}
+
+impl XCBuildResult {
+    /// Get the result of the last reported action
+    pub fn action(&self) -> Option<&XCActionResult> {
+        self.actions.last()
+    }
+
+    /// Get overall outcome of the run
+    pub fn outcome(&self) -> XCBuildOutcome {
+        XCBuildOutcome::new(self)
+    }
+
+    /// Get the `n` slowest steps, slowest first
+    pub fn slowest_steps(&self, n: usize) -> Vec<&XCStep> {
+        let mut steps = self
+            .steps
+            .iter()
+            .filter(|s| s.duration.is_some())
+            .collect::<Vec<_>>();
+        steps.sort_by_key(|s| std::cmp::Reverse(s.duration));
+        steps.truncate(n);
+        steps
+    }
+
+    /// Get the `n` slowest type-checked function bodies and expressions, slowest first.
+    ///
+    /// Locations reported more than once, e.g. for each architecture, are counted once with
+    /// their slowest time.
+    pub fn slowest_type_checks(&self, n: usize) -> Vec<&XCSlowTypeCheck> {
+        let mut checks: Vec<&XCSlowTypeCheck> = vec![];
+        for check in self.slow_type_checks.iter() {
+            let same_location = checks
+                .iter_mut()
+                .find(|c| c.path == check.path && c.line == check.line && c.column == check.column);
+            match same_location {
+                Some(c) if c.ms < check.ms => *c = check,
+                Some(_) => {}
+                None => checks.push(check),
+            }
+        }
+        checks.sort_by(|a, b| b.ms.total_cmp(&a.ms));
+        checks.truncate(n);
+        checks
+    }
+}
@@ -0,0 +1,78 @@
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// xcodebuild action reported by a `** <ACTION> SUCCEEDED/FAILED **` marker
+#[derive(
+    Debug, Clone, PartialEq, Eq, Serialize, Deserialize, derive_is_enum_variant::is_enum_variant,
+)]
+pub enum XCAction {
+    /// `build`
+    Build,
+    /// `build-for-testing`
+    BuildForTesting,
+    /// `test`
+    Test,
+    /// `test-without-building`
+    TestWithoutBuilding,
+    /// `archive`
+    Archive,
+    /// `-exportArchive`
+    Export,
+    /// `analyze`
+    Analyze,
+    /// `clean`
+    Clean,
+    /// `install`
+    Install,
+    /// Any other action name as printed
+    Other(String),
+}
+
+impl XCAction {
+    /// Get action from the name printed in `** <ACTION> SUCCEEDED **`
+    pub fn from_marker(name: &str) -> Self {
+        match name.trim() {
+            "BUILD" => Self::Build,
+            "TEST BUILD" => Self::BuildForTesting,
+            "TEST" => Self::Test,
+            "TEST EXECUTE" => Self::TestWithoutBuilding,
+            "ARCHIVE" => Self::Archive,
+            "EXPORT" => Self::Export,
+            "ANALYZE" => Self::Analyze,
+            "CLEAN" => Self::Clean,
+            "INSTALL" => Self::Install,
+            name => Self::Other(name.to_string()),
+        }
+    }
+}
+
+/// Outcome of an xcodebuild action
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    derive_is_enum_variant::is_enum_variant,
+)]
+pub enum XCActionOutcome {
+    /// `** <ACTION> SUCCEEDED **`
+    Succeeded,
+    /// `** <ACTION> FAILED **`
+    Failed,
+}
+
+/// Result of an xcodebuild action
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct XCActionResult {
+    /// Action that finished
+    pub action: XCAction,
+    /// Whether the action succeeded
+    pub outcome: XCActionOutcome,
+    /// Produced artifact, e.g. the export directory from `Exported <name> to: <path>`
+    pub artifact: Option<PathBuf>,
+    /// Errors reported for the action, e.g. `error: exportArchive: ...`
+    pub errors: Vec<String>,
+}
@@ -0,0 +1,46 @@
+use lazy_regex::{regex, regex_captures as cap};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// Command listed under `The following build commands failed:`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct XCFailedCommand {
+    /// Build step type, e.g. `CompileSwift` or `Ld`
+    pub step: String,
+    /// File the step operated on, if any
+    pub file: Option<PathBuf>,
+    /// Target the step belongs to
+    pub target: Option<String>,
+    /// Project the target belongs to
+    pub project: Option<String>,
+    /// Command line as printed by xcodebuild
+    pub command: String,
+}
+
+impl XCFailedCommand {
+    /// Create failed command from summary line, e.g.
+    /// `CompileSwift normal arm64 /path/File.swift (in target 'X' from project 'Y')`
+    pub fn new(line: &str) -> Option<Self> {
+        let command = line.trim();
+        let (_, step, args) = cap!(r"^(\w+)\s?(.*)$", command)?;
+        let (args, target, project) = match cap!(
+            r"^(.*?)\s?\(in target '([^']+)' from project '([^']+)'\)$",
+            args
+        ) {
+            Some((_, args, target, project)) => (args, Some(target), Some(project)),
+            None => (args, None, None),
+        };
+        let file = regex!(r"/(?:\\ |\S)+")
+            .find_iter(args)
+            .last()
+            .map(|m| PathBuf::from(m.as_str().replace("\\ ", " ")));
+
+        Some(Self {
+            step: step.to_string(),
+            file,
+            target: target.map(ToString::to_string),
+            project: project.map(ToString::to_string),
+            command: command.to_string(),
+        })
+    }
+}
@@ -0,0 +1,136 @@
+use crate::XCDestination;
+use lazy_regex::regex_captures as cap;
+use serde::{Deserialize, Serialize};
+
+/// `xcodebuild: error:` caused by a broken invocation rather than a compilation failure
+#[derive(
+    Debug, Clone, PartialEq, Eq, Serialize, Deserialize, derive_is_enum_variant::is_enum_variant,
+)]
+pub enum XCInvocationError {
+    /// `Unable to find a destination matching the provided destination specifier`
+    DestinationNotFound {
+        /// Requested destination
+        specifier: XCDestination,
+        /// Destinations listed under `Available destinations for the "X" scheme:`
+        available: Vec<XCDestination>,
+        /// Destinations listed under `Ineligible destinations for the "X" scheme:`
+        ineligible: Vec<XCDestination>,
+    },
+    /// `The project named "X" does not contain a scheme named "Y"`
+    SchemeNotFound {
+        /// Project or workspace name
+        container: String,
+        /// Requested scheme
+        scheme: String,
+    },
+    /// `Scheme X is not currently configured for the test action`
+    SchemeNotConfigured {
+        /// Requested scheme
+        scheme: String,
+        /// Requested action
+        action: String,
+    },
+    /// `Could not resolve package dependencies:`
+    PackageResolutionFailed {
+        /// Reasons listed after the error
+        reasons: Vec<String>,
+    },
+    /// `'X.xcodeproj' does not exist` or no project found in the directory
+    ProjectNotFound {
+        /// Project, workspace or directory path
+        path: String,
+    },
+    /// `Unknown build action 'X'`
+    UnknownAction {
+        /// Requested action
+        action: String,
+    },
+    /// `invalid option '-X'`
+    InvalidOption {
+        /// Invalid option
+        option: String,
+    },
+    /// Any other `xcodebuild: error:`
+    Other {
+        /// Error message
+        message: String,
+    },
+}
+
+impl XCInvocationError {
+    /// Create invocation error from `xcodebuild: error:` message and the indented lines following it
+    pub fn new(message: &str, body: &[String]) -> Self {
+        let message = message.trim();
+
+        if message.starts_with("Unable to find a destination matching") {
+            let content = body.join("\n");
+            let specifier = body
+                .iter()
+                .take_while(|l| !l.trim().ends_with("scheme:"))
+                .find_map(|l| XCDestination::from_listing_line(l))
+                .unwrap_or_default();
+            let available = XCDestination::available(&content);
+            let ineligible = XCDestination::ineligible(&content);
+            return Self::DestinationNotFound {
+                specifier,
+                available,
+                ineligible,
+            };
+        }
+
+        if message.starts_with("Could not resolve package dependencies") {
+            let reasons = body
+                .iter()
+                .map(|l| l.trim())
+                .filter(|l| !l.is_empty())
+                .map(ToString::to_string)
+                .collect();
+            return Self::PackageResolutionFailed { reasons };
+        }
+
+        if let Some((_, container, scheme)) = cap!(
+            r#"The (?:project|workspace) named "([^"]+)" does not contain a scheme named "([^"]+)""#,
+            message
+        ) {
+            return Self::SchemeNotFound {
+                container: container.into(),
+                scheme: scheme.into(),
+            };
+        }
+
+        if let Some((_, scheme, action)) = cap!(
+            r"Scheme (.+) is not currently configured for the (\w+) action",
+            message
+        ) {
+            return Self::SchemeNotConfigured {
+                scheme: scheme.into(),
+                action: action.into(),
+            };
+        }
+
+        if let Some((_, path)) = cap!(r"^'(.+)' does not exist", message).or_else(|| {
+            cap!(
+                r"^The directory (.+) does not contain an Xcode project",
+                message
+            )
+        }) {
+            return Self::ProjectNotFound { path: path.into() };
+        }
+
+        if let Some((_, action)) = cap!(r"^Unknown build action '(.+)'", message) {
+            return Self::UnknownAction {
+                action: action.into(),
+            };
+        }
+
+        if let Some((_, option)) = cap!(r"^invalid option '(.+)'", message) {
+            return Self::InvalidOption {
+                option: option.into(),
+            };
+        }
+
+        Self::Other {
+            message: message.to_string(),
+        }
+    }
+}
@@ -0,0 +1,19 @@
+use super::XCDiagnosticSeverity;
+use serde::{Deserialize, Serialize};
+
+/// Rule violation reported by a linter such as SwiftLint or SwiftFormat
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct XCLintDiagnostic {
+    /// Linter that reported the violation, e.g. `SwiftLint`
+    pub tool: String,
+    /// Rule identifier, e.g. `line_length`
+    pub rule: String,
+    /// Diagnostic severity
+    pub severity: XCDiagnosticSeverity,
+    /// `file:line:column` location
+    pub location: String,
+    /// Diagnostic message
+    pub message: String,
+    /// Target of the script phase that ran the linter, if any
+    pub target: Option<String>,
+}
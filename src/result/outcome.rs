@@ -0,0 +1,83 @@
+use super::XCBuildResult;
+use crate::XCCancelReason;
+use serde::{Deserialize, Serialize};
+
+/// Overall outcome of an xcodebuild run, combining its exit status with the parsed log
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    derive_is_enum_variant::is_enum_variant,
+)]
+pub enum XCBuildOutcome {
+    /// All actions succeeded
+    Succeeded,
+    /// Building failed, exit code 65 or a failed build marker
+    BuildFailed,
+    /// Tests ran and some failed or crashed
+    TestsFailed,
+    /// Testing failed without any failing test, e.g. the test host could not launch
+    TestsNotRun,
+    /// Invalid arguments or project setup, exit code 64
+    UsageError,
+    /// xcodebuild internal error, exit code 70
+    InternalError,
+    /// Any other non-zero exit code
+    Failed(i32),
+    /// xcodebuild was terminated by given signal
+    Killed(i32),
+    /// Build was stopped by [`crate::XCLogger`]
+    Cancelled(XCCancelReason),
+    /// Neither an exit code nor an action marker was reported, e.g. for a truncated log
+    Unknown,
+}
+
+impl XCBuildOutcome {
+    /// Get outcome of given build result
+    pub fn new(result: &XCBuildResult) -> Self {
+        if let Some(reason) = result.cancelled {
+            return Self::Cancelled(reason);
+        }
+        if let Some(signal) = result.signal {
+            return Self::Killed(signal);
+        }
+
+        match result.exit_code {
+            Some(0) => return Self::Succeeded,
+            Some(64) => return Self::UsageError,
+            Some(70) => return Self::InternalError,
+            _ => (),
+        }
+
+        if result.tests.failed().next().is_some() {
+            return Self::TestsFailed;
+        }
+
+        if let Some(action) = result.action() {
+            let runs_tests = action.action.is_test() || action.action.is_test_without_building();
+            match action.outcome.is_failed() {
+                true if runs_tests && result.failed_commands.is_empty() => {
+                    return Self::TestsNotRun
+                }
+                true => return Self::BuildFailed,
+                false if result.exit_code.is_none() => return Self::Succeeded,
+                false => (),
+            }
+        }
+
+        if !result.invocation_errors.is_empty() {
+            return Self::UsageError;
+        }
+
+        match result.exit_code {
+            Some(65) => Self::BuildFailed,
+            Some(code) => Self::Failed(code),
+            None if !result.failed_commands.is_empty() => Self::BuildFailed,
+            None => Self::Unknown,
+        }
+    }
+}
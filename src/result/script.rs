@@ -0,0 +1,45 @@
+use serde::{Deserialize, Serialize};
+
+/// Severity of a diagnostic
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    derive_is_enum_variant::is_enum_variant,
+)]
+pub enum XCDiagnosticSeverity {
+    /// Warning
+    Warning,
+    /// Error
+    Error,
+}
+
+/// `error:` or `warning:` line printed by a run script build phase
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct XCScriptDiagnostic {
+    /// Diagnostic severity
+    pub severity: XCDiagnosticSeverity,
+    /// `file:line:column` location, if the line has one
+    pub location: Option<String>,
+    /// Diagnostic message
+    pub message: String,
+}
+
+/// Output of a `PhaseScriptExecution` step
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct XCScriptOutput {
+    /// Script phase name, e.g. `Run SwiftLint`
+    pub name: String,
+    /// Target the script phase belongs to
+    pub target: String,
+    /// Project the script phase belongs to
+    pub project: String,
+    /// Raw lines printed by the script
+    pub output: Vec<String>,
+    /// Errors and warnings found in the script output
+    pub diagnostics: Vec<XCScriptDiagnostic>,
+}
@@ -0,0 +1,143 @@
+use serde::{Deserialize, Serialize};
+
+/// Status of a single test case
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    derive_is_enum_variant::is_enum_variant,
+)]
+pub enum XCTestStatus {
+    /// Test case started and no result was reported yet
+    Started,
+    /// Test case passed
+    Passed,
+    /// Test case failed
+    Failed,
+    /// Test host exited unexpectedly while running the test case
+    Crashed,
+    /// Test case failed, then passed when retried
+    Flaky,
+}
+
+impl XCTestStatus {
+    /// Whether the test case failed or crashed
+    pub fn is_failure(&self) -> bool {
+        matches!(self, Self::Failed | Self::Crashed)
+    }
+}
+
+/// Crash details of a test case whose test host exited unexpectedly
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct XCTestCrash {
+    /// Signal the test host was terminated with, if reported
+    pub signal: Option<String>,
+    /// `Fatal error:` and `Thread N Crashed` lines printed before the restart
+    pub messages: Vec<String>,
+}
+
+/// Single test case result
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct XCTestCase {
+    /// Test suite, e.g. `AppTests.FooTests`
+    pub suite: String,
+    /// Test case name, e.g. `testBar`
+    pub name: String,
+    /// Current status
+    pub status: XCTestStatus,
+    /// Reported duration in seconds
+    pub duration: Option<f64>,
+    /// Failure messages reported for the test case
+    pub failures: Vec<String>,
+    /// Crash details when status is [`XCTestStatus::Crashed`]
+    pub crash: Option<XCTestCrash>,
+}
+
+impl XCTestCase {
+    fn new(suite: &str, name: &str) -> Self {
+        Self {
+            suite: suite.to_string(),
+            name: name.to_string(),
+            status: XCTestStatus::Started,
+            duration: None,
+            failures: vec![],
+            crash: None,
+        }
+    }
+
+    /// Whether the test case has the given suite and name.
+    ///
+    /// Suite names printed without module prefix, e.g. in `Restarting after unexpected exit`
+    /// lines, are matched against the module qualified suite name.
+    pub fn is(&self, suite: &str, name: &str) -> bool {
+        self.name == name && (self.suite == suite || self.suite.ends_with(&format!(".{suite}")))
+    }
+
+    /// Identifier passed to `-only-testing`, e.g. `AppTests/FooTests/testBar`.
+    ///
+    /// Only available for module qualified suite names, as the module names the test target.
+    /// See [`XCTestCase::identifier_in`] for suites printed without module, e.g. by parallel or
+    /// Objective-C tests.
+    pub fn identifier(&self) -> Option<String> {
+        let (target, suite) = self.suite.split_once('.')?;
+        Some(format!("{target}/{suite}/{}", self.name))
+    }
+
+    /// Identifier passed to `-only-testing` for a suite of given test target
+    pub fn identifier_in(&self, target: &str) -> String {
+        let suite = self.suite.rsplit('.').next().unwrap_or_default();
+        format!("{target}/{suite}/{}", self.name)
+    }
+}
+
+/// Test cases collected from a build log in the order they started
+#[derive(Debug, Default, Clone, Serialize, Deserialize, derive_deref_rs::Deref)]
+pub struct XCTestResults(Vec<XCTestCase>);
+
+impl XCTestResults {
+    /// Get test case by suite and name
+    pub fn get(&self, suite: &str, name: &str) -> Option<&XCTestCase> {
+        self.0.iter().rev().find(|case| case.is(suite, name))
+    }
+
+    /// Get test cases with the given status
+    pub fn with_status(&self, status: XCTestStatus) -> impl Iterator<Item = &XCTestCase> {
+        self.0.iter().filter(move |case| case.status == status)
+    }
+
+    /// Get test cases that failed or crashed
+    pub fn failed(&self) -> impl Iterator<Item = &XCTestCase> {
+        self.0.iter().filter(|case| case.status.is_failure())
+    }
+
+    /// Get the test case that was started last and has not finished yet
+    pub fn running(&self) -> Option<&XCTestCase> {
+        self.0.iter().rev().find(|case| case.status.is_started())
+    }
+
+    pub(crate) fn start(&mut self, suite: &str, name: &str) {
+        self.0.push(XCTestCase::new(suite, name));
+    }
+
+    /// Get test case by suite and name, creating it if it was never reported as started.
+    pub(crate) fn get_mut(&mut self, suite: &str, name: &str) -> &mut XCTestCase {
+        match self.0.iter().rposition(|case| case.is(suite, name)) {
+            Some(idx) => &mut self.0[idx],
+            None => {
+                self.start(suite, name);
+                self.0.last_mut().unwrap()
+            }
+        }
+    }
+
+    pub(crate) fn running_mut(&mut self) -> Option<&mut XCTestCase> {
+        self.0
+            .iter_mut()
+            .rev()
+            .find(|case| case.status.is_started())
+    }
+}
@@ -0,0 +1,45 @@
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+
+/// Build step such as `Compile`, `Linking`, `CodeSign` or `PhaseScriptExecution`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct XCStep {
+    /// Step kind, e.g. `Compile`
+    pub kind: String,
+    /// Target the step belongs to
+    pub target: String,
+    /// Formatted step description, e.g. `[App] Compiling AppDelegate.swift`
+    pub description: String,
+    /// When the step header was read
+    pub started: SystemTime,
+    /// Time until the next step on the same target, or the end of the log
+    pub duration: Option<Duration>,
+}
+
+/// Aggregated task timing from the `-showBuildTimingSummary` Build Timing Summary block
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct XCTaskTiming {
+    /// Task type, e.g. `CompileSwiftSources`
+    pub task: String,
+    /// Number of tasks of this type
+    pub count: usize,
+    /// Total time spent in seconds
+    pub seconds: f64,
+}
+
+/// Function body or expression reported by `-warn-long-function-bodies`,
+/// `-warn-long-expression-type-checking` or `-debug-time-function-bodies`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct XCSlowTypeCheck {
+    /// Source file path
+    pub path: PathBuf,
+    /// Line number
+    pub line: usize,
+    /// Column number
+    pub column: usize,
+    /// Symbol as printed by swiftc, e.g. `instance method 'foo()'`
+    pub symbol: String,
+    /// Time spent type-checking in milliseconds
+    pub ms: f64,
+}
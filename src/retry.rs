@@ -0,0 +1,231 @@
+use crate::{XCBuildCommand, XCBuildResult, XCEvent, XCLogger, XCRunner};
+use crate::{XCTestCase, XCTestResults, XCTestStatus};
+use async_stream::stream;
+use process_stream::{Stream, StreamExt};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+
+/// Event of a test run driven by [`XCTestRetry`]
+#[derive(Debug, Clone, derive_is_enum_variant::is_enum_variant)]
+pub enum XCRetryEvent {
+    /// Event yielded by the logger of given attempt, `0` being the initial run
+    Event {
+        /// Attempt index
+        attempt: usize,
+        /// Logger event
+        event: XCEvent,
+    },
+    /// Attempt could not be spawned
+    Failed {
+        /// Attempt index
+        attempt: usize,
+        /// Spawn or argument error
+        error: String,
+    },
+    /// All attempts ended
+    Finished(Box<XCRetryReport>),
+}
+
+/// Test results merged across the initial run and its retries
+#[derive(Debug, Clone, Default)]
+pub struct XCRetryReport {
+    /// Build result of every attempt, starting with the initial run
+    pub attempts: Vec<XCBuildResult>,
+    /// Test cases of all attempts, with tests that passed on retry marked
+    /// [`XCTestStatus::Flaky`]
+    pub tests: XCTestResults,
+}
+
+impl XCRetryReport {
+    /// Get test cases that passed on retry
+    pub fn flaky(&self) -> impl Iterator<Item = &XCTestCase> {
+        self.tests.with_status(XCTestStatus::Flaky)
+    }
+
+    /// Whether the last attempt succeeded, and with it every test that ran before
+    pub fn succeeded(&self) -> bool {
+        self.tests.failed().next().is_none()
+            && self
+                .attempts
+                .last()
+                .is_some_and(|r| r.outcome().is_succeeded())
+    }
+
+    fn merge(&mut self, result: XCBuildResult) {
+        if self.attempts.is_empty() {
+            self.tests = result.tests.clone();
+        } else {
+            for case in result.tests.iter() {
+                let merged = self.tests.get_mut(&case.suite, &case.name);
+                if case.status.is_passed() && merged.status.is_failure() {
+                    merged.status = XCTestStatus::Flaky;
+                    merged.duration = case.duration;
+                } else if merged.status.is_failure() {
+                    *merged = case.clone();
+                }
+            }
+        }
+        self.attempts.push(result);
+    }
+}
+
+/// Run tests, re-running failed ones with `test-without-building -only-testing` up to a number
+/// of times
+pub struct XCTestRetry {
+    runner: XCRunner,
+    root: PathBuf,
+    command: XCBuildCommand,
+    retries: usize,
+    targets: HashMap<String, String>,
+}
+
+impl XCTestRetry {
+    /// Create driver running given test command in root, retrying failed tests up to `retries`
+    /// times
+    pub fn new<P: AsRef<Path>>(root: P, command: XCBuildCommand, retries: usize) -> Self {
+        Self {
+            runner: XCRunner::default(),
+            root: root.as_ref().to_path_buf(),
+            command,
+            retries,
+            targets: HashMap::new(),
+        }
+    }
+
+    /// Set test target of a suite printed without module, e.g. by parallel or Objective-C tests.
+    ///
+    /// Failed tests of such suites are only retried once their target is known.
+    pub fn test_target<S: Into<String>, T: Into<String>>(mut self, suite: S, target: T) -> Self {
+        self.targets.insert(suite.into(), target.into());
+        self
+    }
+
+    /// Run xcodebuild configured by [`XCRunner`]
+    pub fn runner(mut self, runner: XCRunner) -> Self {
+        self.runner = runner;
+        self
+    }
+
+    /// Run tests and stream events of every attempt, ending with [`XCRetryEvent::Finished`].
+    pub fn spawn_and_stream(self) -> Pin<Box<dyn Stream<Item = XCRetryEvent> + Send>> {
+        stream! {
+            let mut report = XCRetryReport::default();
+            let mut command = self.command.clone();
+
+            for attempt in 0..=self.retries {
+                let logger = command
+                    .args()
+                    .and_then(|args| XCLogger::new_with_runner(&self.runner, &self.root, args));
+                let mut events = match logger.and_then(|mut l| Ok(l.spawn_and_stream_events()?)) {
+                    Ok(events) => events,
+                    Err(e) => {
+                        yield XCRetryEvent::Failed { attempt, error: e.to_string() };
+                        break;
+                    }
+                };
+
+                while let Some(event) = events.next().await {
+                    if let XCEvent::Finished(ref result) = event {
+                        report.merge(*result.clone());
+                    }
+                    yield XCRetryEvent::Event { attempt, event }
+                }
+
+                // Only retry tests whose last run failed and that can be selected by identifier
+                let failed = match report.attempts.last() {
+                    Some(result) => result.tests.failed().filter_map(|c| self.identifier(c)).collect::<Vec<_>>(),
+                    None => vec![],
+                };
+                if failed.is_empty() {
+                    break;
+                }
+                command = self.command.retry(attempt + 1, failed);
+            }
+
+            yield XCRetryEvent::Finished(Box::new(report))
+        }
+        .boxed()
+    }
+
+    /// Get `-only-testing` identifier of a test case, warning about suites of unknown target
+    fn identifier(&self, case: &XCTestCase) -> Option<String> {
+        let identifier = case.identifier().or_else(|| {
+            let target = self.targets.get(&case.suite)?;
+            Some(case.identifier_in(target))
+        });
+        if identifier.is_none() {
+            tracing::warn!(
+                "Unable to retry {}.{}: unknown test target, see XCTestRetry::test_target",
+                case.suite,
+                case.name
+            );
+        }
+        identifier
+    }
+}
+
+#[cfg(unix)]
+#[tokio::test]
+#[tracing_test::traced_test]
+async fn test_retry() {
+    use crate::XCBuildAction;
+
+    let script = crate::runner::StandIn::new(
+        r#"#!/bin/sh
+run() {
+  echo "Test Case '-[AppTests.FooTests $1]' started."
+  echo "Test Case '-[AppTests.FooTests $1]' $2 (0.010 seconds)."
+}
+case "$*" in
+  *test-without-building*)
+    case "$*" in *-only-testing:AppTests/FooTests/testBar*) run testBar passed ;; esac
+    case "$*" in *-only-testing:AppTests/BarTests/testQuux*) echo "Test case 'BarTests.testQuux()' passed on 'Clone 1 of iPhone 14 - App (12345)' (0.010 seconds)" ;; esac
+    run testBaz failed
+    echo "** TEST EXECUTE FAILED **"
+    exit 65 ;;
+  *)
+    run testBar failed
+    run testBaz failed
+    run testQux passed
+    echo "Test case 'BarTests.testQuux()' failed on 'Clone 1 of iPhone 14 - App (12345)' (0.010 seconds)"
+    echo "Test Case '-[LegacyTests testOld]' failed (0.010 seconds)."
+    echo "** TEST FAILED **"
+    exit 65 ;;
+esac
+"#,
+    );
+
+    let command = XCBuildCommand::new([XCBuildAction::Test]).scheme("App");
+    let events = XCTestRetry::new(std::env::temp_dir(), command, 3)
+        .runner(script.runner())
+        .test_target("BarTests", "AppTests")
+        .spawn_and_stream()
+        .collect::<Vec<_>>()
+        .await;
+
+    let report = match events.last() {
+        Some(XCRetryEvent::Finished(report)) => report,
+        event => panic!("Unexpected {event:?}"),
+    };
+    let status = |name| report.tests.get("AppTests.FooTests", name).unwrap().status;
+
+    assert_eq!(report.attempts.len(), 4);
+    assert_eq!(report.attempts[0].tests.len(), 5);
+    assert_eq!(report.attempts[1].tests.len(), 3);
+    assert_eq!(report.attempts[2].tests.len(), 1);
+    assert_eq!(status("testBar"), XCTestStatus::Flaky);
+    assert_eq!(status("testBaz"), XCTestStatus::Failed);
+    assert_eq!(status("testQux"), XCTestStatus::Passed);
+    assert_eq!(
+        report.tests.get("BarTests", "testQuux").unwrap().status,
+        XCTestStatus::Flaky
+    );
+    assert_eq!(
+        report.tests.get("LegacyTests", "testOld").unwrap().status,
+        XCTestStatus::Failed
+    );
+    assert_eq!(report.flaky().count(), 2);
+    assert!(logs_contain("Unable to retry LegacyTests.testOld"));
+    assert!(!report.succeeded());
+}
@@ -0,0 +1,139 @@
+use anyhow::{bail, Result};
+use std::ffi::{OsStr, OsString};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Executable and environment used to run xcodebuild
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct XCRunner {
+    /// Path to xcodebuild or a stand-in executable
+    pub program: PathBuf,
+    /// Whether to resolve xcodebuild through `xcrun`
+    pub xcrun: bool,
+    /// Xcode installation to use, passed as `DEVELOPER_DIR`
+    pub developer_dir: Option<PathBuf>,
+    /// Extra environment variables
+    pub envs: Vec<(OsString, OsString)>,
+}
+
+impl Default for XCRunner {
+    fn default() -> Self {
+        Self::new("/usr/bin/xcodebuild")
+    }
+}
+
+impl XCRunner {
+    /// Create runner executing the given program
+    pub fn new<P: AsRef<Path>>(program: P) -> Self {
+        Self {
+            program: program.as_ref().to_path_buf(),
+            xcrun: false,
+            developer_dir: None,
+            envs: vec![],
+        }
+    }
+
+    /// Create runner resolving xcodebuild with `xcrun xcodebuild`
+    pub fn xcrun() -> Self {
+        Self {
+            xcrun: true,
+            ..Self::new("/usr/bin/xcrun")
+        }
+    }
+
+    /// Select Xcode installation, e.g. `/Applications/Xcode-beta.app/Contents/Developer`
+    pub fn developer_dir<P: AsRef<Path>>(mut self, path: P) -> Self {
+        self.developer_dir = Some(path.as_ref().to_path_buf());
+        self
+    }
+
+    /// Add environment variable
+    pub fn env<K, V>(mut self, key: K, value: V) -> Self
+    where
+        K: AsRef<OsStr>,
+        V: AsRef<OsStr>,
+    {
+        self.envs
+            .push((key.as_ref().to_os_string(), value.as_ref().to_os_string()));
+        self
+    }
+
+    /// Add environment variables
+    pub fn envs<I, K, V>(self, vars: I) -> Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: AsRef<OsStr>,
+        V: AsRef<OsStr>,
+    {
+        vars.into_iter()
+            .fold(self, |runner, (k, v)| runner.env(k, v))
+    }
+
+    /// Create command running xcodebuild in given root
+    pub fn command<P: AsRef<Path>>(&self, root: P) -> Command {
+        let mut command = Command::new(&self.program);
+        if self.xcrun {
+            command.arg("xcodebuild");
+        }
+        if let Some(ref developer_dir) = self.developer_dir {
+            command.env("DEVELOPER_DIR", developer_dir);
+        }
+        command.envs(self.envs.iter().map(|(k, v)| (k, v)));
+        command.current_dir(root);
+        command
+    }
+
+    /// Run xcodebuild with given arguments, returning stdout or failing with stderr
+    pub(crate) async fn output<P, I, S>(&self, root: P, args: I) -> Result<String>
+    where
+        P: AsRef<Path>,
+        I: IntoIterator<Item = S>,
+        S: AsRef<OsStr>,
+    {
+        let mut process = tokio::process::Command::from(self.command(root));
+        let output = process.args(args).output().await?;
+        if !output.status.success() {
+            bail!(String::from_utf8(output.stderr)?)
+        }
+        Ok(String::from_utf8(output.stdout)?)
+    }
+}
+
+/// Get path in the temporary directory that no other test, in this or a parallel run, uses
+#[cfg(test)]
+pub(crate) fn temp_path(extension: &str) -> PathBuf {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+    let id = COUNTER.fetch_add(1, Ordering::Relaxed);
+    std::env::temp_dir().join(format!("xclog-{}-{id}.{extension}", std::process::id()))
+}
+
+/// Shell script standing in for xcodebuild in tests, removed once dropped
+#[cfg(all(test, unix))]
+pub(crate) struct StandIn(PathBuf);
+
+#[cfg(all(test, unix))]
+impl StandIn {
+    /// Write executable script with given content
+    pub(crate) fn new(content: &str) -> Self {
+        use std::os::unix::fs::PermissionsExt;
+
+        let path = temp_path("sh");
+        std::fs::write(&path, content).unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+        Self(path)
+    }
+
+    /// Get runner running the script instead of xcodebuild
+    pub(crate) fn runner(&self) -> XCRunner {
+        XCRunner::new(&self.0)
+    }
+}
+
+#[cfg(all(test, unix))]
+impl Drop for StandIn {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.0);
+    }
+}
@@ -0,0 +1,232 @@
+use crate::XCRunner;
+use anyhow::{Context, Result};
+use lazy_regex::regex_captures as cap;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// macOS SDK path used when xcodebuild doesn't report one
+const MACOSX_SDK_PATH: &str =
+    "/Applications/Xcode.app/Contents/Developer/Platforms/MacOSX.platform/Developer/SDKs/MacOSX.sdk";
+
+/// SDK reported by `xcodebuild -showsdks`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct XCSdk {
+    /// Platform, e.g. `iphonesimulator`
+    pub platform: String,
+    /// Display name, e.g. `Simulator - iOS 17.2`
+    pub display_name: String,
+    /// Identifier passed to `-sdk`, e.g. `iphonesimulator17.2`
+    pub identifier: String,
+    /// SDK version, e.g. `17.2`
+    pub version: String,
+    /// Path to the SDK, only reported by `-showsdks -json`
+    pub path: Option<PathBuf>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct XCSdkJson {
+    canonical_name: String,
+    display_name: String,
+    platform: String,
+    sdk_version: Option<String>,
+    sdk_path: Option<PathBuf>,
+}
+
+impl XCSdk {
+    /// Get path to the macOS SDK of the selected Xcode, see [`XCSdk::macosx_path_with_runner`]
+    pub fn macosx_path() -> PathBuf {
+        Self::macosx_path_with_runner(&XCRunner::default())
+    }
+
+    /// Get path to the macOS SDK reported by `-showsdks -json` of xcodebuild configured by
+    /// [`XCRunner`], falling back to the SDK of `/Applications/Xcode.app`.
+    ///
+    /// Blocks until xcodebuild exits, as it's used to build compile arguments synchronously.
+    pub fn macosx_path_with_runner(runner: &XCRunner) -> PathBuf {
+        runner
+            .command(".")
+            .args(["-showsdks", "-json"])
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .and_then(|output| String::from_utf8(output.stdout).ok())
+            .and_then(|content| Self::from_json(&content).ok())
+            .and_then(|sdks| sdks.into_iter().find(|sdk| sdk.platform == "macosx")?.path)
+            .unwrap_or_else(|| PathBuf::from(MACOSX_SDK_PATH))
+    }
+
+    /// Get installed SDKs
+    pub async fn list() -> Result<Vec<Self>> {
+        Self::list_with_runner(&XCRunner::default()).await
+    }
+
+    /// Get installed SDKs using xcodebuild configured by [`XCRunner`]
+    pub async fn list_with_runner(runner: &XCRunner) -> Result<Vec<Self>> {
+        Self::from_json(&runner.output(".", ["-showsdks", "-json"]).await?)
+    }
+
+    /// Parse `xcodebuild -showsdks -json` output
+    pub fn from_json(content: &str) -> Result<Vec<Self>> {
+        let content = &content[content.find('[').context("Missing JSON array")?..];
+        let sdks: Vec<XCSdkJson> = serde_json::from_str(content)?;
+
+        Ok(sdks
+            .into_iter()
+            .map(|sdk| {
+                let version = sdk
+                    .sdk_version
+                    .unwrap_or_else(|| split_identifier(&sdk.canonical_name).1.to_string());
+                Self {
+                    platform: sdk.platform,
+                    display_name: sdk.display_name,
+                    identifier: sdk.canonical_name,
+                    version,
+                    path: sdk.sdk_path,
+                }
+            })
+            .collect())
+    }
+
+    /// Parse `xcodebuild -showsdks` output, e.g. `iOS 17.2    -sdk iphoneos17.2` lines
+    pub fn from_lines<'a, I: IntoIterator<Item = &'a str>>(lines: I) -> Vec<Self> {
+        lines
+            .into_iter()
+            .filter_map(|line| {
+                let (_, display_name, identifier) = cap!(r"^\s*(.*?)\s+-sdk\s+(\S+)\s*$", line)?;
+                let (platform, version) = split_identifier(identifier);
+                Some(Self {
+                    platform: platform.to_string(),
+                    display_name: display_name.to_string(),
+                    identifier: identifier.to_string(),
+                    version: version.to_string(),
+                    path: None,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Split SDK identifier, e.g. `iphoneos17.2`, into platform and version
+fn split_identifier(identifier: &str) -> (&str, &str) {
+    let index = identifier
+        .find(|c: char| c.is_ascii_digit())
+        .unwrap_or(identifier.len());
+    identifier.split_at(index)
+}
+
+/// Xcode version reported by `xcodebuild -version`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct XCVersion {
+    /// Xcode version, e.g. `15.2`
+    pub version: String,
+    /// Build number, e.g. `15C500b`
+    pub build: String,
+}
+
+impl XCVersion {
+    /// Get selected Xcode version
+    pub async fn new() -> Result<Self> {
+        Self::new_with_runner(&XCRunner::default()).await
+    }
+
+    /// Get Xcode version using xcodebuild configured by [`XCRunner`]
+    pub async fn new_with_runner(runner: &XCRunner) -> Result<Self> {
+        Self::from_lines(runner.output(".", ["-version"]).await?.lines())
+    }
+
+    /// Parse `xcodebuild -version` output
+    pub fn from_lines<'a, I: IntoIterator<Item = &'a str>>(lines: I) -> Result<Self> {
+        let (mut version, mut build) = (None, None);
+        for line in lines {
+            if let Some((_, value)) = cap!(r"^Xcode\s+(\S+)", line.trim()) {
+                version = Some(value.to_string());
+            } else if let Some((_, value)) = cap!(r"^Build version\s+(\S+)", line.trim()) {
+                build = Some(value.to_string());
+            }
+        }
+
+        Ok(Self {
+            version: version.context("Missing Xcode version")?,
+            build: build.context("Missing build version")?,
+        })
+    }
+}
+
+#[test]
+fn test_sdks_from_lines() {
+    let content = "iOS SDKs:
+\tiOS 17.2                      \t-sdk iphoneos17.2
+
+iOS Simulator SDKs:
+\tSimulator - iOS 17.2          \t-sdk iphonesimulator17.2
+
+macOS SDKs:
+\tmacOS 14.2                    \t-sdk macosx14.2
+\t[OBSOLETE] DriverKit 23.2     \t-sdk driverkit23.2
+";
+    let sdks = XCSdk::from_lines(content.lines());
+
+    assert_eq!(sdks.len(), 4);
+    assert_eq!(sdks[1].platform, "iphonesimulator");
+    assert_eq!(sdks[1].display_name, "Simulator - iOS 17.2");
+    assert_eq!(sdks[1].identifier, "iphonesimulator17.2");
+    assert_eq!(sdks[1].version, "17.2");
+    assert_eq!(sdks[3].display_name, "[OBSOLETE] DriverKit 23.2");
+}
+
+#[test]
+fn test_sdks_from_json() {
+    let content = r#"[
+  {
+    "buildID" : "A1B2C3",
+    "canonicalName" : "macosx14.2",
+    "displayName" : "macOS 14.2",
+    "isBaseSdk" : true,
+    "platform" : "macosx",
+    "platformPath" : "/Applications/Xcode.app/Contents/Developer/Platforms/MacOSX.platform",
+    "platformVersion" : "14.2",
+    "productBuildVersion" : "23C53",
+    "productName" : "macOS",
+    "productVersion" : "14.2",
+    "sdkPath" : "/Applications/Xcode.app/Contents/Developer/Platforms/MacOSX.platform/Developer/SDKs/MacOSX14.2.sdk",
+    "sdkVersion" : "14.2"
+  }
+]"#;
+    let sdks = XCSdk::from_json(content).unwrap();
+
+    assert_eq!(sdks[0].platform, "macosx");
+    assert_eq!(sdks[0].identifier, "macosx14.2");
+    assert_eq!(sdks[0].version, "14.2");
+    assert!(sdks[0].path.as_ref().unwrap().ends_with("MacOSX14.2.sdk"));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_macosx_path() {
+    let script = crate::runner::StandIn::new(
+        "#!/bin/sh\n\
+        echo '[{\"canonicalName\": \"macosx14.2\", \"displayName\": \"macOS 14.2\", \"platform\": \"macosx\", \"sdkPath\": \"/path/to/MacOSX14.2.sdk\"}]'\n",
+    );
+    let failing = crate::runner::StandIn::new("#!/bin/sh\nexit 1\n");
+
+    assert_eq!(
+        XCSdk::macosx_path_with_runner(&script.runner()),
+        PathBuf::from("/path/to/MacOSX14.2.sdk")
+    );
+    assert_eq!(
+        XCSdk::macosx_path_with_runner(&failing.runner()),
+        PathBuf::from(MACOSX_SDK_PATH)
+    );
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_version() {
+    let script =
+        crate::runner::StandIn::new("#!/bin/sh\necho 'Xcode 15.2'\necho 'Build version 15C500b'\n");
+    let version = XCVersion::new_with_runner(&script.runner()).await.unwrap();
+
+    assert_eq!(version.version, "15.2");
+    assert_eq!(version.build, "15C500b");
+}
@@ -0,0 +1,304 @@
+use crate::{XCBuildCommand, XCBuildResult, XCEvent, XCLogger, XCRunner};
+use anyhow::Result;
+use async_stream::stream;
+use process_stream::{Stream, StreamExt};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::time::{Duration, SystemTime};
+use tokio::time::Instant;
+
+/// Default glob selecting files whose changes trigger a rebuild
+const DEFAULT_PATTERN: &str = "**/*.swift";
+/// Default time between two scans of the watched files
+const DEFAULT_POLL_INTERVAL: Duration = Duration::from_millis(500);
+/// Default time without further changes before rebuilding
+const DEFAULT_SETTLE: Duration = Duration::from_millis(300);
+
+/// Event of a build cycle driven by [`XCWatch`]
+#[derive(Debug, Clone, derive_is_enum_variant::is_enum_variant)]
+pub enum XCWatchEvent {
+    /// Build cycle started, previous diagnostics are outdated
+    CycleStarted {
+        /// Cycle index, `0` being the initial build
+        cycle: usize,
+        /// Files changed since the previous cycle started
+        paths: Vec<PathBuf>,
+    },
+    /// Event yielded by the logger of given cycle
+    Event {
+        /// Cycle index
+        cycle: usize,
+        /// Logger event
+        event: XCEvent,
+    },
+    /// Build cycle ended, either on its own or cancelled by new changes
+    CycleFinished {
+        /// Cycle index
+        cycle: usize,
+        /// Build result, or the error raised while spawning the build
+        result: Result<Box<XCBuildResult>, String>,
+    },
+}
+
+/// Watch a project root and rebuild once changes to matching files settle, cancelling the
+/// in-flight build when new changes arrive.
+pub struct XCWatch {
+    runner: XCRunner,
+    root: PathBuf,
+    command: XCBuildCommand,
+    patterns: Vec<String>,
+    poll_interval: Duration,
+    settle: Duration,
+    grace_period: Option<Duration>,
+}
+
+impl XCWatch {
+    /// Create watcher running given command in root whenever `**/*.swift` files change
+    pub fn new<P: AsRef<Path>>(root: P, command: XCBuildCommand) -> Self {
+        Self {
+            runner: XCRunner::default(),
+            root: root.as_ref().to_path_buf(),
+            command,
+            patterns: vec![DEFAULT_PATTERN.into()],
+            poll_interval: DEFAULT_POLL_INTERVAL,
+            settle: DEFAULT_SETTLE,
+            grace_period: None,
+        }
+    }
+
+    /// Run xcodebuild configured by [`XCRunner`]
+    pub fn runner(mut self, runner: XCRunner) -> Self {
+        self.runner = runner;
+        self
+    }
+
+    /// Watch files matching given `wax` globs, relative to root, instead of `**/*.swift`
+    pub fn patterns<I, S>(mut self, patterns: I) -> Result<Self>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.patterns = patterns.into_iter().map(Into::into).collect();
+        for pattern in self.patterns.iter() {
+            wax::Glob::new(pattern).map_err(|e| anyhow::anyhow!("{pattern}: {e}"))?;
+        }
+        Ok(self)
+    }
+
+    /// Set time between two scans of the watched files
+    pub fn poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    /// Set time without further changes to wait for before rebuilding
+    pub fn settle(mut self, settle: Duration) -> Self {
+        self.settle = settle;
+        self
+    }
+
+    /// Set time a cancelled build gets to stop before it is killed
+    pub fn grace_period(mut self, grace_period: Duration) -> Self {
+        self.grace_period = Some(grace_period);
+        self
+    }
+
+    /// Build, then rebuild on changes, streaming events of every cycle. The stream never ends on
+    /// its own; drop it to stop watching.
+    pub fn spawn_and_stream(self) -> Pin<Box<dyn Stream<Item = XCWatchEvent> + Send>> {
+        stream! {
+            let mut snapshot = self.snapshot().await;
+            let mut paths = vec![];
+
+            for cycle in 0.. {
+                yield XCWatchEvent::CycleStarted { cycle, paths: std::mem::take(&mut paths) };
+
+                let mut logger = match self.logger() {
+                    Ok(logger) => logger,
+                    Err(error) => {
+                        yield XCWatchEvent::CycleFinished { cycle, result: Err(error.to_string()) };
+                        self.wait_for_changes(&mut snapshot, &mut paths).await;
+                        continue;
+                    }
+                };
+                let cancel = logger.cancel_handle();
+                let mut events = match logger.spawn_and_stream_events() {
+                    Ok(events) => events,
+                    Err(error) => {
+                        yield XCWatchEvent::CycleFinished { cycle, result: Err(error.to_string()) };
+                        self.wait_for_changes(&mut snapshot, &mut paths).await;
+                        continue;
+                    }
+                };
+
+                let mut result = Err("Build did not finish".to_string());
+                let mut ticker = tokio::time::interval(self.poll_interval);
+                loop {
+                    let event = tokio::select! {
+                        event = events.next() => event,
+                        _ = ticker.tick() => {
+                            let changed = self.changes(&mut snapshot).await;
+                            if !changed.is_empty() {
+                                if paths.is_empty() {
+                                    tracing::debug!("Cancelling cycle {cycle}: {changed:?}");
+                                    cancel.cancel();
+                                }
+                                paths.extend(changed);
+                            }
+                            continue;
+                        }
+                    };
+                    match event {
+                        Some(event) => {
+                            if let XCEvent::Finished(ref finished) = event {
+                                result = Ok(finished.clone());
+                            }
+                            yield XCWatchEvent::Event { cycle, event }
+                        }
+                        None => break,
+                    }
+                }
+
+                yield XCWatchEvent::CycleFinished { cycle, result };
+                self.wait_for_changes(&mut snapshot, &mut paths).await;
+            }
+        }
+        .boxed()
+    }
+
+    fn logger(&self) -> Result<XCLogger> {
+        let mut logger = XCLogger::new_with_runner(&self.runner, &self.root, self.command.args()?)?;
+        if let Some(grace_period) = self.grace_period {
+            logger.set_grace_period(grace_period);
+        }
+        Ok(logger)
+    }
+
+    /// Wait until changes, including already collected `paths`, settle.
+    async fn wait_for_changes(
+        &self,
+        snapshot: &mut HashMap<PathBuf, SystemTime>,
+        paths: &mut Vec<PathBuf>,
+    ) {
+        let mut last_change = Instant::now();
+        loop {
+            tokio::time::sleep(self.poll_interval).await;
+            let changed = self.changes(snapshot).await;
+            if !changed.is_empty() {
+                last_change = Instant::now();
+                paths.extend(changed);
+            } else if !paths.is_empty() && last_change.elapsed() >= self.settle {
+                paths.sort();
+                paths.dedup();
+                return;
+            }
+        }
+    }
+
+    /// Update snapshot, returning added, modified and removed paths.
+    async fn changes(&self, snapshot: &mut HashMap<PathBuf, SystemTime>) -> Vec<PathBuf> {
+        let current = self.snapshot().await;
+        let mut changed = current
+            .iter()
+            .filter(|(path, modified)| snapshot.get(*path) != Some(modified))
+            .map(|(path, _)| path.clone())
+            .collect::<Vec<_>>();
+        changed.extend(
+            snapshot
+                .keys()
+                .filter(|path| !current.contains_key(*path))
+                .cloned(),
+        );
+        *snapshot = current;
+        changed
+    }
+
+    /// Get modification times of files matching the watched globs.
+    async fn snapshot(&self) -> HashMap<PathBuf, SystemTime> {
+        let root = self.root.clone();
+        let patterns = self.patterns.clone();
+        tokio::task::spawn_blocking(move || {
+            patterns
+                .iter()
+                .filter_map(|pattern| wax::walk(pattern, &root).ok())
+                .flatten()
+                .flatten()
+                .filter_map(|entry| {
+                    let modified = entry.metadata().ok()?.modified().ok()?;
+                    Some((entry.into_path(), modified))
+                })
+                .collect()
+        })
+        .await
+        .unwrap_or_default()
+    }
+}
+
+#[cfg(unix)]
+#[tokio::test]
+#[tracing_test::traced_test]
+async fn test_watch() {
+    use crate::XCBuildAction;
+
+    let root = crate::runner::temp_path("watch");
+    std::fs::create_dir_all(root.join("Sources")).unwrap();
+    std::fs::write(root.join("Sources/App.swift"), "").unwrap();
+    std::fs::write(root.join("README.md"), "").unwrap();
+
+    let script = crate::runner::StandIn::new(
+        "#!/bin/sh\n\
+        trap 'kill $pid; echo \"** BUILD INTERRUPTED **\"; exit 130' INT\n\
+        [ -f Sources/Feature.swift ] || { sleep 5 & pid=$!; wait $pid; }\n\
+        echo \"** BUILD SUCCEEDED **\"\n",
+    );
+
+    let mut events = XCWatch::new(&root, XCBuildCommand::new([XCBuildAction::Build]))
+        .runner(script.runner())
+        .patterns(["**/*.swift"])
+        .unwrap()
+        .poll_interval(Duration::from_millis(50))
+        .settle(Duration::from_millis(100))
+        .spawn_and_stream();
+
+    let mut seen = vec![];
+    while let Some(event) = events.next().await {
+        if let XCWatchEvent::CycleStarted { cycle: 0, .. } = event {
+            let root = root.clone();
+            tokio::spawn(async move {
+                tokio::time::sleep(Duration::from_millis(300)).await;
+                std::fs::write(root.join("README.md"), "changed").unwrap();
+                std::fs::write(root.join("Sources/Feature.swift"), "").unwrap();
+            });
+        }
+        let done = matches!(event, XCWatchEvent::CycleFinished { cycle: 1, .. });
+        seen.push(event);
+        if done {
+            break;
+        }
+    }
+    std::fs::remove_dir_all(root.clone()).unwrap();
+
+    let finished = seen
+        .iter()
+        .filter_map(|e| match e {
+            XCWatchEvent::CycleFinished { result, .. } => Some(result.as_ref().unwrap()),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    let started = seen
+        .iter()
+        .filter_map(|e| match e {
+            XCWatchEvent::CycleStarted { cycle, paths } => Some((*cycle, paths.clone())),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    assert!(seen[0].is_cycle_started());
+    assert_eq!(
+        started,
+        vec![(0, vec![]), (1, vec![root.join("Sources/Feature.swift")])]
+    );
+    assert!(finished[0].outcome().is_cancelled());
+    assert!(finished[1].outcome().is_succeeded());
+}
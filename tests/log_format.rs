@@ -20,9 +20,9 @@ async fn get_stream(lines: Vec<String>) -> Pin<Box<dyn Stream<Item = XCOutput> +
                         _ => ()
                     }
                 },
-                ProcessItem::Exit(status) => yield XCOutput {
-                    value: format!("[Exit] {status}"), kind: XCOutputTask::Result
-                }
+                ProcessItem::Exit(status) => yield XCOutput::new(
+                    XCOutputTask::Result, format!("[Exit] {status}")
+                )
             }
         }
     }